@@ -0,0 +1,84 @@
+use std::fmt;
+
+/// 勘定科目の識別子。
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct AccountId(String);
+
+impl fmt::Display for AccountId {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl From<&str> for AccountId {
+  fn from(code: &str) -> Self {
+    Self::new(code)
+  }
+}
+
+impl AccountId {
+  pub fn new(code: &str) -> Self {
+    Self(code.to_string())
+  }
+
+  pub fn code(&self) -> &str {
+    &self.0
+  }
+}
+
+/// 借方か貸方か。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Side {
+  Debit,
+  Credit,
+}
+
+/// 勘定科目の区分。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AccountType {
+  Asset,
+  Liability,
+  Equity,
+  Revenue,
+  Expense,
+}
+
+impl AccountType {
+  /// 残高が増える側。
+  pub fn normal_side(&self) -> Side {
+    match self {
+      AccountType::Asset | AccountType::Expense => Side::Debit,
+      AccountType::Liability | AccountType::Equity | AccountType::Revenue => Side::Credit,
+    }
+  }
+}
+
+/// 勘定科目。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Account {
+  id: AccountId,
+  name: String,
+  account_type: AccountType,
+}
+
+impl Account {
+  pub fn new(id: AccountId, name: &str, account_type: AccountType) -> Self {
+    Self {
+      id,
+      name: name.to_string(),
+      account_type,
+    }
+  }
+
+  pub fn as_id(&self) -> &AccountId {
+    &self.id
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn as_account_type(&self) -> &AccountType {
+    &self.account_type
+  }
+}
@@ -0,0 +1,144 @@
+use iso_4217::CurrencyCode;
+use rust_decimal::Decimal;
+
+use crate::accounting::{AccountId, LedgerError, Side};
+use crate::money::{Allotment, Money};
+
+/// 仕訳。借方と貸方の明細からなり、通貨ごとに借方合計と貸方合計が一致する。
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+  description: String,
+  debits: Vec<Allotment<AccountId>>,
+  credits: Vec<Allotment<AccountId>>,
+}
+
+impl JournalEntry {
+  /// コンストラクタ。
+  ///
+  /// 借方・貸方のいずれかが空の場合、金額が正でない明細がある場合、
+  /// または通貨ごとに貸借が一致しない場合はエラーを返す。
+  pub fn new(
+    description: &str,
+    debits: Vec<Allotment<AccountId>>,
+    credits: Vec<Allotment<AccountId>>,
+  ) -> Result<Self, LedgerError> {
+    if debits.is_empty() || credits.is_empty() {
+      return Err(LedgerError::EmptyEntryError);
+    }
+    if debits
+      .iter()
+      .chain(credits.iter())
+      .any(|e| !e.amount.is_positive())
+    {
+      return Err(LedgerError::NonPositiveAmountError);
+    }
+    let mut currencies: Vec<CurrencyCode> = vec![];
+    for allotment in debits.iter().chain(credits.iter()) {
+      if !currencies.contains(&allotment.amount.currency) {
+        currencies.push(allotment.amount.currency);
+      }
+    }
+    if currencies
+      .iter()
+      .any(|currency| Self::sum_of(&debits, currency) != Self::sum_of(&credits, currency))
+    {
+      return Err(LedgerError::UnbalancedEntryError);
+    }
+    Ok(Self {
+      description: description.to_string(),
+      debits,
+      credits,
+    })
+  }
+
+  /// 借方・貸方それぞれ1行ずつの仕訳を生成する。
+  pub fn simple(
+    description: &str,
+    debit: AccountId,
+    credit: AccountId,
+    amount: Money,
+  ) -> Result<Self, LedgerError> {
+    Self::new(
+      description,
+      vec![Allotment::new(debit, amount.clone())],
+      vec![Allotment::new(credit, amount)],
+    )
+  }
+
+  fn sum_of(allotments: &[Allotment<AccountId>], currency: &CurrencyCode) -> Decimal {
+    allotments
+      .iter()
+      .filter(|e| e.amount.currency == *currency)
+      .map(|e| e.amount.amount)
+      .sum()
+  }
+
+  pub fn description(&self) -> &str {
+    &self.description
+  }
+
+  pub fn as_debits(&self) -> &[Allotment<AccountId>] {
+    &self.debits
+  }
+
+  pub fn as_credits(&self) -> &[Allotment<AccountId>] {
+    &self.credits
+  }
+
+  /// 全明細を、貸借の別とともに返す。
+  pub fn lines(&self) -> impl Iterator<Item = (Side, &Allotment<AccountId>)> {
+    self
+      .debits
+      .iter()
+      .map(|e| (Side::Debit, e))
+      .chain(self.credits.iter().map(|e| (Side::Credit, e)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+
+  use crate::accounting::{AccountId, JournalEntry, LedgerError};
+  use crate::money::{Allotment, Money};
+
+  fn line(code: &str, amount: i32) -> Allotment<AccountId> {
+    Allotment::new(AccountId::new(code), Money::dollars_i32(amount))
+  }
+
+  #[test]
+  fn test01_balanced() {
+    let entry = JournalEntry::new(
+      "sale with tax",
+      vec![line("cash", 110)],
+      vec![line("sales", 100), line("tax payable", 10)],
+    )
+    .unwrap();
+    assert_eq!(entry.lines().count(), 3);
+    assert_eq!(entry.description(), "sale with tax");
+  }
+
+  #[test]
+  fn test02_errors() {
+    assert_eq!(
+      JournalEntry::new("x", vec![line("cash", 100)], vec![line("sales", 90)]),
+      Err(LedgerError::UnbalancedEntryError)
+    );
+    assert_eq!(
+      JournalEntry::new("x", vec![], vec![line("sales", 90)]),
+      Err(LedgerError::EmptyEntryError)
+    );
+    assert_eq!(
+      JournalEntry::new("x", vec![line("cash", 0)], vec![line("sales", 0)]),
+      Err(LedgerError::NonPositiveAmountError)
+    );
+    let yen = Allotment::new(
+      AccountId::new("cash"),
+      Money::from((100u32, CurrencyCode::JPY)),
+    );
+    assert_eq!(
+      JournalEntry::new("x", vec![yen], vec![line("sales", 100)]),
+      Err(LedgerError::UnbalancedEntryError)
+    );
+  }
+}
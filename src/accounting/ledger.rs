@@ -0,0 +1,269 @@
+use std::collections::HashMap;
+
+use iso_4217::CurrencyCode;
+
+use crate::accounting::{Account, AccountId, JournalEntry, LedgerError, Side};
+use crate::intervals::{Interval, LimitValue};
+use crate::money::{Allotment, Allotments, Money, MoneyFan};
+use crate::time::TimePoint;
+
+/// 記帳済みの仕訳。
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostedEntry {
+  pub time_point: TimePoint,
+  pub entry: JournalEntry,
+}
+
+/// 総勘定元帳。
+#[derive(Debug, Clone, Default)]
+pub struct Ledger {
+  accounts: HashMap<AccountId, Account>,
+  entries: Vec<PostedEntry>,
+}
+
+impl Ledger {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// 勘定科目を開設する。同じ識別子の勘定科目が既にある場合はエラーを返す。
+  pub fn open_account(&mut self, account: Account) -> Result<(), LedgerError> {
+    if self.accounts.contains_key(account.as_id()) {
+      return Err(LedgerError::DuplicateAccountError);
+    }
+    self.accounts.insert(account.as_id().clone(), account);
+    Ok(())
+  }
+
+  pub fn account(&self, id: &AccountId) -> Option<&Account> {
+    self.accounts.get(id)
+  }
+
+  /// 仕訳を `time_point` の時点で記帳する。未開設の勘定科目を含む場合はエラーを返す。
+  pub fn post(&mut self, time_point: TimePoint, entry: JournalEntry) -> Result<(), LedgerError> {
+    if entry
+      .lines()
+      .any(|(_, e)| !self.accounts.contains_key(&e.entity))
+    {
+      return Err(LedgerError::UnknownAccountError);
+    }
+    self.entries.push(PostedEntry { time_point, entry });
+    Ok(())
+  }
+
+  pub fn entries(&self) -> impl Iterator<Item = &PostedEntry> {
+    self.entries.iter()
+  }
+
+  /// 時点 `time_point` までに記帳された仕訳による、勘定科目 `id` の `currency` 建て残高を返す。
+  ///
+  /// 残高は勘定科目の区分に応じて、増える側を正として表す。
+  pub fn balance_as_of(
+    &self,
+    id: &AccountId,
+    time_point: &TimePoint,
+    currency: CurrencyCode,
+  ) -> Result<Money, LedgerError> {
+    self.balance_where(id, currency, |e| !e.is_after(time_point))
+  }
+
+  /// 期間 `interval` に記帳された仕訳による、勘定科目 `id` の `currency` 建て残高の増減を返す。
+  pub fn balance_during(
+    &self,
+    id: &AccountId,
+    interval: &Interval<TimePoint>,
+    currency: CurrencyCode,
+  ) -> Result<Money, LedgerError> {
+    self.balance_where(id, currency, |e| {
+      interval.includes(&LimitValue::Limit(e.clone()))
+    })
+  }
+
+  /// 時点 `time_point` における `currency` 建ての試算表を、借方残高を正として返す。
+  ///
+  /// 残高がゼロの勘定科目は含めない。含まれる勘定科目の合計はゼロになるが、
+  /// 該当する仕訳がない場合などは空の試算表を返し、その [MoneyFan::total] はエラーになる。
+  pub fn trial_balance_as_of(
+    &self,
+    time_point: &TimePoint,
+    currency: CurrencyCode,
+  ) -> MoneyFan<AccountId> {
+    let allotments = self
+      .entries
+      .iter()
+      .filter(|e| !e.time_point.is_after(time_point))
+      .flat_map(|e| e.entry.lines())
+      .filter(|(_, e)| e.amount.currency == currency)
+      .map(|(side, e)| match side {
+        Side::Debit => e.clone(),
+        Side::Credit => e.clone().negated(),
+      })
+      .collect::<Vec<Allotment<AccountId>>>();
+    let mut fan = MoneyFan::new(
+      Allotments::new(allotments).expect("allotments are filtered to a single currency"),
+    );
+    fan.retain(|e| !e.amount.is_zero());
+    fan
+  }
+
+  fn balance_where<F>(
+    &self,
+    id: &AccountId,
+    currency: CurrencyCode,
+    predicate: F,
+  ) -> Result<Money, LedgerError>
+  where
+    F: Fn(&TimePoint) -> bool,
+  {
+    let account = self
+      .accounts
+      .get(id)
+      .ok_or(LedgerError::UnknownAccountError)?;
+    let normal_side = account.as_account_type().normal_side();
+    Ok(
+      self
+        .entries
+        .iter()
+        .filter(|e| predicate(&e.time_point))
+        .flat_map(|e| e.entry.lines())
+        .filter(|(_, e)| e.entity == *id && e.amount.currency == currency)
+        .fold(Money::zero(currency), |acc, (side, e)| {
+          if side == normal_side {
+            acc + e.amount.clone()
+          } else {
+            acc - e.amount.clone()
+          }
+        }),
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+
+  use crate::accounting::{Account, AccountId, AccountType, JournalEntry, Ledger, LedgerError};
+  use crate::intervals::{Interval, LimitValue};
+  use crate::money::{Allotment, Money, MoneyError};
+  use crate::time::TimePoint;
+
+  fn day(month: u32, day: u32) -> TimePoint {
+    TimePoint::at_ymd_hms_milli_utc(2024, month, day, 0, 0, 0, 0)
+  }
+
+  fn id(code: &str) -> AccountId {
+    AccountId::new(code)
+  }
+
+  fn ledger() -> Ledger {
+    let mut ledger = Ledger::new();
+    for (code, account_type) in [
+      ("cash", AccountType::Asset),
+      ("capital", AccountType::Equity),
+      ("sales", AccountType::Revenue),
+      ("rent", AccountType::Expense),
+    ]
+    .iter()
+    {
+      ledger
+        .open_account(Account::new(id(code), code, *account_type))
+        .unwrap();
+    }
+    let entries = vec![
+      (day(1, 1), "capital", "cash", "capital", 1000),
+      (day(1, 15), "sale", "cash", "sales", 300),
+      (day(2, 1), "rent", "rent", "cash", 200),
+      (day(2, 20), "sale", "cash", "sales", 500),
+    ];
+    for (time_point, description, debit, credit, amount) in entries {
+      let entry = JournalEntry::simple(
+        description,
+        id(debit),
+        id(credit),
+        Money::dollars_i32(amount),
+      )
+      .unwrap();
+      ledger.post(time_point, entry).unwrap();
+    }
+    ledger
+  }
+
+  #[test]
+  fn test01_balance_as_of() {
+    let ledger = ledger();
+    let usd = CurrencyCode::USD;
+    assert_eq!(
+      ledger.balance_as_of(&id("cash"), &day(1, 31), usd),
+      Ok(Money::dollars_i32(1300))
+    );
+    assert_eq!(
+      ledger.balance_as_of(&id("cash"), &day(2, 1), usd),
+      Ok(Money::dollars_i32(1100))
+    );
+    assert_eq!(
+      ledger.balance_as_of(&id("sales"), &day(12, 31), usd),
+      Ok(Money::dollars_i32(800))
+    );
+    assert_eq!(
+      ledger.balance_as_of(&id("cash"), &day(12, 31), CurrencyCode::JPY),
+      Ok(Money::zero(CurrencyCode::JPY))
+    );
+    assert_eq!(
+      ledger.balance_as_of(&id("unknown"), &day(12, 31), usd),
+      Err(LedgerError::UnknownAccountError)
+    );
+  }
+
+  #[test]
+  fn test02_balance_during() {
+    let ledger = ledger();
+    let february = Interval::over(
+      LimitValue::Limit(day(2, 1)),
+      true,
+      LimitValue::Limit(day(3, 1)),
+      false,
+    );
+    assert_eq!(
+      ledger.balance_during(&id("cash"), &february, CurrencyCode::USD),
+      Ok(Money::dollars_i32(300))
+    );
+    assert_eq!(
+      ledger.balance_during(&id("rent"), &february, CurrencyCode::USD),
+      Ok(Money::dollars_i32(200))
+    );
+  }
+
+  #[test]
+  fn test03_trial_balance() {
+    let ledger = ledger();
+    let trial_balance = ledger.trial_balance_as_of(&day(12, 31), CurrencyCode::USD);
+    assert_eq!(
+      trial_balance.amount_of(&id("capital")),
+      Some(&Money::dollars_i32(-1000))
+    );
+    assert_eq!(trial_balance.total(), Ok(Money::dollars_i32(0)));
+
+    let trial_balance = ledger.trial_balance_as_of(&day(12, 31), CurrencyCode::JPY);
+    assert!(trial_balance.iter().next().is_none());
+    assert_eq!(trial_balance.total(), Err(MoneyError::EmptyError));
+  }
+
+  #[test]
+  fn test04_post_errors() {
+    let mut ledger = ledger();
+    let entry = JournalEntry::new(
+      "unknown",
+      vec![Allotment::new(id("cash"), Money::dollars_i32(1))],
+      vec![Allotment::new(id("loan"), Money::dollars_i32(1))],
+    )
+    .unwrap();
+    assert_eq!(
+      ledger.post(day(3, 1), entry),
+      Err(LedgerError::UnknownAccountError)
+    );
+    assert_eq!(
+      ledger.open_account(Account::new(id("cash"), "cash", AccountType::Asset)),
+      Err(LedgerError::DuplicateAccountError)
+    );
+  }
+}
@@ -0,0 +1,17 @@
+mod account;
+mod journal_entry;
+mod ledger;
+
+pub use self::account::*;
+pub use self::journal_entry::*;
+pub use self::ledger::*;
+
+#[derive(Debug, PartialEq)]
+pub enum LedgerError {
+  EmptyEntryError,
+  NonPositiveAmountError,
+  /// 通貨ごとの借方合計と貸方合計が一致しない。
+  UnbalancedEntryError,
+  UnknownAccountError,
+  DuplicateAccountError,
+}
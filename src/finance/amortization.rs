@@ -0,0 +1,320 @@
+use std::convert::TryFrom;
+
+use chrono::Utc;
+use rust_decimal::{Decimal, MathematicalOps, RoundingStrategy};
+
+use crate::finance::FinanceError;
+use crate::money::Money;
+use crate::time::{CalendarDate, Duration};
+use crate::util::Percentage;
+
+/// 返済方式。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum AmortizationMethod {
+  /// 元利均等返済
+  EqualInstallment,
+  /// 元金均等返済
+  EqualPrincipal,
+}
+
+/// 返済予定表の1回分の行。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AmortizationRow {
+  pub number: u32,
+  pub date: CalendarDate,
+  pub payment: Money,
+  pub principal: Money,
+  pub interest: Money,
+  pub balance: Money,
+}
+
+/// 月次返済の返済予定表。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AmortizationSchedule(Vec<AmortizationRow>);
+
+impl AmortizationSchedule {
+  /// コンストラクタ。
+  ///
+  /// `start` の翌月から毎月返済する。`start` が月末日の場合、返済日も各月の月末日とする。
+  /// 各回の金額は通貨の桁数で丸め、端数は最終回で調整する。丸めた元金が残高を超える回は残高を返済する。
+  /// 期間 `term` が月単位でない場合、1ヶ月未満の場合、または回数が `u32` に収まらない場合はエラーを返す。
+  pub fn new(
+    principal: Money,
+    annual_rate: Percentage,
+    term: &Duration,
+    start: CalendarDate,
+    method: AmortizationMethod,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Self, FinanceError> {
+    if term.as_unit().is_convertible_to_milliseconds() || term.in_base_units() < 1 {
+      return Err(FinanceError::InvalidTermError);
+    }
+    let periods =
+      u32::try_from(term.in_base_units()).map_err(|_| FinanceError::InvalidTermError)?;
+    let monthly_rate = annual_rate.to_factor() / Decimal::from(12);
+
+    let installment = Self::installment(&principal, monthly_rate, periods, rounding_strategy);
+    let principal_portion = principal
+      .clone()
+      .divided_by(Decimal::from(periods))
+      .rounded(rounding_strategy);
+
+    let mut balance = principal;
+    let mut rows = Vec::with_capacity(periods as usize);
+    for number in 1..=periods {
+      let interest = balance
+        .clone()
+        .times(monthly_rate)
+        .rounded(rounding_strategy);
+      let principal = match method {
+        AmortizationMethod::EqualInstallment => installment.clone() - interest.clone(),
+        AmortizationMethod::EqualPrincipal => principal_portion.clone(),
+      };
+      // 切り上げた元金で残高が負にならないよう、残高を超える分は返済しない
+      let principal = if number == periods || principal.amount.abs() > balance.amount.abs() {
+        balance.clone()
+      } else {
+        principal
+      };
+      balance = balance - principal.clone();
+      rows.push(AmortizationRow {
+        number,
+        date: Self::payment_date(&start, number),
+        payment: principal.clone() + interest.clone(),
+        principal,
+        interest,
+        balance: balance.clone(),
+      });
+    }
+    Ok(Self(rows))
+  }
+
+  fn installment(
+    principal: &Money,
+    monthly_rate: Decimal,
+    periods: u32,
+    rounding_strategy: RoundingStrategy,
+  ) -> Money {
+    if monthly_rate.is_zero() {
+      return principal
+        .clone()
+        .divided_by(Decimal::from(periods))
+        .rounded(rounding_strategy);
+    }
+    let factor = (Decimal::ONE + monthly_rate).powi(periods as i64);
+    principal
+      .clone()
+      .times(monthly_rate * factor)
+      .divided_by(factor - Decimal::ONE)
+      .rounded(rounding_strategy)
+  }
+
+  fn payment_date(start: &CalendarDate, number: u32) -> CalendarDate {
+    let date = start.add_months(number as i64, Utc);
+    if start.is_last_day_of_month() {
+      date.last_day_of_month()
+    } else {
+      date
+    }
+  }
+
+  pub fn rows(&self) -> &[AmortizationRow] {
+    &self.0
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, AmortizationRow> {
+    self.0.iter()
+  }
+
+  /// 返済総額を返す。
+  pub fn total_payment(&self) -> Money {
+    self.sum_by(|e| &e.payment)
+  }
+
+  /// 利息の総額を返す。
+  pub fn total_interest(&self) -> Money {
+    self.sum_by(|e| &e.interest)
+  }
+
+  fn sum_by<F>(&self, f: F) -> Money
+  where
+    F: Fn(&AmortizationRow) -> &Money,
+  {
+    let currency = self.0[0].payment.currency;
+    self
+      .0
+      .iter()
+      .fold(Money::zero(currency), |acc, e| acc + f(e).clone())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::RoundingStrategy;
+
+  use crate::finance::{AmortizationMethod, AmortizationSchedule, FinanceError};
+  use crate::money::Money;
+  use crate::time::{CalendarDate, Duration};
+  use crate::util::Percentage;
+
+  fn usd(s: &str) -> Money {
+    Money::from((s, CurrencyCode::USD))
+  }
+
+  fn yen(amount: u32) -> Money {
+    Money::from((amount, CurrencyCode::JPY))
+  }
+
+  #[test]
+  fn test01_equal_installment() {
+    let schedule = AmortizationSchedule::new(
+      usd("100000"),
+      Percentage::from_str("6%").unwrap(),
+      &Duration::months(12),
+      CalendarDate::from((2024, 1, 15)),
+      AmortizationMethod::EqualInstallment,
+      RoundingStrategy::MidpointAwayFromZero,
+    )
+    .unwrap();
+    let rows = schedule.rows();
+    assert_eq!(rows.len(), 12);
+
+    let first = &rows[0];
+    assert_eq!(first.date, CalendarDate::from((2024, 2, 15)));
+    assert_eq!(first.payment, usd("8606.64"));
+    assert_eq!(first.interest, usd("500.00"));
+    assert_eq!(first.principal, usd("8106.64"));
+    assert_eq!(first.balance, usd("91893.36"));
+
+    let last = &rows[11];
+    assert_eq!(last.date, CalendarDate::from((2025, 1, 15)));
+    assert!(last.balance.is_zero());
+    assert!(rows[..11].iter().all(|e| e.payment == usd("8606.64")));
+    assert_eq!(
+      schedule.total_payment(),
+      usd("100000") + schedule.total_interest()
+    );
+  }
+
+  #[test]
+  fn test02_equal_principal() {
+    let schedule = AmortizationSchedule::new(
+      yen(1_200_000),
+      Percentage::from_str("1.2%").unwrap(),
+      &Duration::years(1),
+      CalendarDate::from((2024, 4, 1)),
+      AmortizationMethod::EqualPrincipal,
+      RoundingStrategy::ToZero,
+    )
+    .unwrap();
+    assert!(schedule.iter().all(|e| e.principal == yen(100_000)));
+    assert_eq!(schedule.rows()[0].interest, yen(1_200));
+    assert_eq!(schedule.rows()[11].interest, yen(100));
+    assert_eq!(schedule.total_interest(), yen(7_800));
+
+    let schedule = AmortizationSchedule::new(
+      yen(1_000_000),
+      Percentage::zero(),
+      &Duration::months(12),
+      CalendarDate::from((2024, 4, 1)),
+      AmortizationMethod::EqualPrincipal,
+      RoundingStrategy::ToZero,
+    )
+    .unwrap();
+    assert_eq!(schedule.rows()[0].payment, yen(83_333));
+    assert_eq!(schedule.rows()[11].payment, yen(83_337));
+    assert_eq!(schedule.total_payment(), yen(1_000_000));
+  }
+
+  #[test]
+  fn test03_end_of_month() {
+    let schedule = AmortizationSchedule::new(
+      yen(300_000),
+      Percentage::from_str("3%").unwrap(),
+      &Duration::months(3),
+      CalendarDate::from((2024, 1, 31)),
+      AmortizationMethod::EqualInstallment,
+      RoundingStrategy::MidpointAwayFromZero,
+    )
+    .unwrap();
+    let dates = schedule.iter().map(|e| e.date.clone()).collect::<Vec<_>>();
+    assert_eq!(
+      dates,
+      vec![
+        CalendarDate::from((2024, 2, 29)),
+        CalendarDate::from((2024, 3, 31)),
+        CalendarDate::from((2024, 4, 30)),
+      ]
+    );
+
+    let schedule = AmortizationSchedule::new(
+      yen(300_000),
+      Percentage::from_str("3%").unwrap(),
+      &Duration::months(2),
+      CalendarDate::from((2023, 12, 30)),
+      AmortizationMethod::EqualInstallment,
+      RoundingStrategy::MidpointAwayFromZero,
+    )
+    .unwrap();
+    assert_eq!(schedule.rows()[0].date, CalendarDate::from((2024, 1, 30)));
+    assert_eq!(schedule.rows()[1].date, CalendarDate::from((2024, 2, 29)));
+  }
+
+  #[test]
+  fn test04_invalid_term() {
+    assert_eq!(
+      AmortizationSchedule::new(
+        yen(1),
+        Percentage::zero(),
+        &Duration::days(30),
+        CalendarDate::from((2024, 1, 1)),
+        AmortizationMethod::EqualInstallment,
+        RoundingStrategy::ToZero,
+      ),
+      Err(FinanceError::InvalidTermError)
+    );
+    assert_eq!(
+      AmortizationSchedule::new(
+        yen(1),
+        Percentage::zero(),
+        &Duration::months(4_294_967_297),
+        CalendarDate::from((2024, 1, 1)),
+        AmortizationMethod::EqualInstallment,
+        RoundingStrategy::ToZero,
+      ),
+      Err(FinanceError::InvalidTermError)
+    );
+  }
+
+  #[test]
+  fn test05_rounded_up_principal() {
+    // 9円を6回で返済すると毎回1.5円を2円に切り上げるため、5回目で残高がなくなる
+    for method in [
+      AmortizationMethod::EqualPrincipal,
+      AmortizationMethod::EqualInstallment,
+    ] {
+      let schedule = AmortizationSchedule::new(
+        yen(9),
+        Percentage::zero(),
+        &Duration::months(6),
+        CalendarDate::from((2024, 1, 1)),
+        method,
+        RoundingStrategy::MidpointAwayFromZero,
+      )
+      .unwrap();
+      let principals = schedule
+        .iter()
+        .map(|e| e.principal.clone())
+        .collect::<Vec<_>>();
+      assert_eq!(
+        principals,
+        vec![yen(2), yen(2), yen(2), yen(2), yen(1), yen(0)]
+      );
+      assert!(schedule.iter().all(|e| !e.balance.is_negative()));
+      assert_eq!(schedule.total_payment(), yen(9));
+    }
+  }
+}
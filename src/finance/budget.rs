@@ -0,0 +1,388 @@
+use std::collections::HashMap;
+
+use iso_4217::CurrencyCode;
+use rust_decimal::RoundingStrategy;
+
+use crate::finance::FinanceError;
+use crate::money::{Money, MoneyTimeRate};
+use crate::time::{CalendarYearMonth, Duration, TimePoint};
+
+/// 予算の対象期間。期間の境界は UTC で判定する。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum BudgetPeriod {
+  Month(CalendarYearMonth),
+  Quarter { year: i32, quarter: u32 },
+}
+
+impl From<CalendarYearMonth> for BudgetPeriod {
+  fn from(year_month: CalendarYearMonth) -> Self {
+    BudgetPeriod::Month(year_month)
+  }
+}
+
+impl BudgetPeriod {
+  pub fn month(year: i32, month: u32) -> Self {
+    BudgetPeriod::Month(CalendarYearMonth::from((year, month)))
+  }
+
+  /// 四半期を生成する。`quarter` が 1〜4 でない場合はパニックする。
+  pub fn quarter(year: i32, quarter: u32) -> Self {
+    assert!((1..=4).contains(&quarter), "invalid quarter: {}", quarter);
+    BudgetPeriod::Quarter { year, quarter }
+  }
+
+  /// 年月 `year_month` を含む四半期を返す。
+  pub fn quarter_of(year_month: &CalendarYearMonth) -> Self {
+    Self::quarter(
+      year_month.to_year(),
+      (year_month.to_month_u32() - 1) / 3 + 1,
+    )
+  }
+
+  fn first_month(&self) -> CalendarYearMonth {
+    match self {
+      BudgetPeriod::Month(year_month) => year_month.clone(),
+      BudgetPeriod::Quarter { year, quarter } => {
+        CalendarYearMonth::from((*year, (quarter - 1) * 3 + 1))
+      }
+    }
+  }
+
+  fn months(&self) -> i64 {
+    match self {
+      BudgetPeriod::Month(_) => 1,
+      BudgetPeriod::Quarter { .. } => 3,
+    }
+  }
+
+  fn of_same_kind(&self, year_month: CalendarYearMonth) -> Self {
+    match self {
+      BudgetPeriod::Month(_) => BudgetPeriod::Month(year_month),
+      BudgetPeriod::Quarter { .. } => Self::quarter_of(&year_month),
+    }
+  }
+
+  /// 次の期間を返す。
+  pub fn next(&self) -> Self {
+    self.of_same_kind(self.first_month().add_months(self.months()))
+  }
+
+  /// 前の期間を返す。
+  pub fn previous(&self) -> Self {
+    self.of_same_kind(self.first_month().add_months(-self.months()))
+  }
+
+  /// 期間の開始時点。
+  pub fn start(&self) -> TimePoint {
+    let first = self.first_month();
+    TimePoint::at_ymd_hms_milli_utc(first.to_year(), first.to_month_u32(), 1, 0, 0, 0, 0)
+  }
+
+  /// 期間の終了時点（この時点は含まない）。
+  pub fn end(&self) -> TimePoint {
+    self.next().start()
+  }
+
+  pub fn length(&self) -> Duration {
+    Duration::milliseconds(
+      self.end().milliseconds_from_epoc() - self.start().milliseconds_from_epoc(),
+    )
+  }
+
+  pub fn includes(&self, time_point: &TimePoint) -> bool {
+    !time_point.is_before(&self.start()) && time_point.is_before(&self.end())
+  }
+}
+
+/// 期間ごとの予算額。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Allowance {
+  pub amount: Money,
+  /// 未使用額を次の期間に繰り越すかどうか。
+  pub rollover: bool,
+}
+
+/// 支出の記録。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpendEvent {
+  pub time_point: TimePoint,
+  pub amount: Money,
+}
+
+/// 期間ごとの予算と支出の実績。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+  currency: CurrencyCode,
+  allowances: HashMap<BudgetPeriod, Allowance>,
+  spends: Vec<SpendEvent>,
+}
+
+impl Budget {
+  pub fn new(currency: CurrencyCode) -> Self {
+    Self {
+      currency,
+      allowances: HashMap::new(),
+      spends: vec![],
+    }
+  }
+
+  pub fn as_currency(&self) -> &CurrencyCode {
+    &self.currency
+  }
+
+  /// 期間 `period` に予算額 `amount` を割り当てる。`rollover` が真の場合、未使用額を次の期間に繰り越す。
+  ///
+  /// 通貨が異なる場合、または既に割り当て済みの期間の場合はエラーを返す。
+  pub fn allocate(
+    &mut self,
+    period: BudgetPeriod,
+    amount: Money,
+    rollover: bool,
+  ) -> Result<(), FinanceError> {
+    if amount.currency != self.currency {
+      return Err(FinanceError::NotSameCurrencyError);
+    }
+    if self.allowances.contains_key(&period) {
+      return Err(FinanceError::DuplicatePeriodError);
+    }
+    self
+      .allowances
+      .insert(period, Allowance { amount, rollover });
+    Ok(())
+  }
+
+  /// 支出を記録する。通貨が異なる場合はエラーを返す。
+  pub fn record_spend(&mut self, time_point: TimePoint, amount: Money) -> Result<(), FinanceError> {
+    if amount.currency != self.currency {
+      return Err(FinanceError::NotSameCurrencyError);
+    }
+    self.spends.push(SpendEvent { time_point, amount });
+    Ok(())
+  }
+
+  pub fn spends(&self) -> &[SpendEvent] {
+    &self.spends
+  }
+
+  /// 前の期間から繰り越された額を含む、期間 `period` の予算額を返す。
+  pub fn allowance_for(&self, period: &BudgetPeriod) -> Result<Money, FinanceError> {
+    let allowance = self
+      .allowances
+      .get(period)
+      .ok_or(FinanceError::PeriodNotBudgetedError)?;
+    Ok(allowance.amount.clone() + self.carried_into(period))
+  }
+
+  fn carried_into(&self, period: &BudgetPeriod) -> Money {
+    let previous = period.previous();
+    match self.allowances.get(&previous) {
+      Some(allowance) if allowance.rollover => {
+        let remaining = self.remaining(&previous).unwrap();
+        if remaining.is_positive() {
+          remaining
+        } else {
+          Money::zero(self.currency)
+        }
+      }
+      _ => Money::zero(self.currency),
+    }
+  }
+
+  /// 期間 `period` の支出の合計を返す。
+  pub fn spent_in(&self, period: &BudgetPeriod) -> Money {
+    self.spent_between(&period.start(), &period.end())
+  }
+
+  fn spent_between(&self, start: &TimePoint, end: &TimePoint) -> Money {
+    self
+      .spends
+      .iter()
+      .filter(|e| !e.time_point.is_before(start) && e.time_point.is_before(end))
+      .fold(Money::zero(self.currency), |acc, e| acc + e.amount.clone())
+  }
+
+  /// 期間 `period` の残額を返す。超過している場合は負の金額になる。
+  pub fn remaining(&self, period: &BudgetPeriod) -> Result<Money, FinanceError> {
+    Ok(self.allowance_for(period)? - self.spent_in(period))
+  }
+
+  /// 期間 `period` の開始から時点 `as_of` までの支出のペースを返す。
+  ///
+  /// `as_of` が期間の開始以前の場合はエラーを返す。期間の終了以降の場合は期間全体で計算する。
+  pub fn burn_rate(
+    &self,
+    period: &BudgetPeriod,
+    as_of: &TimePoint,
+  ) -> Result<MoneyTimeRate, FinanceError> {
+    let start = period.start();
+    if !as_of.is_after(&start) {
+      return Err(FinanceError::PeriodNotStartedError);
+    }
+    let end = if as_of.is_before(&period.end()) {
+      as_of.clone()
+    } else {
+      period.end()
+    };
+    let elapsed =
+      Duration::milliseconds(end.milliseconds_from_epoc() - start.milliseconds_from_epoc());
+    MoneyTimeRate::new(self.spent_between(&start, &end), elapsed)
+      .map_err(|_| FinanceError::PeriodNotStartedError)
+  }
+
+  /// 時点 `as_of` までのペースで支出が続いた場合の、期間 `period` 全体の支出見込みを返す。
+  pub fn projected_spend(
+    &self,
+    period: &BudgetPeriod,
+    as_of: &TimePoint,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, FinanceError> {
+    let rate = self.burn_rate(period, as_of)?;
+    Ok(rate.over(&period.length(), rounding_strategy).unwrap())
+  }
+
+  /// 支出見込みが予算額を超える額を返す。超えない場合はゼロ。
+  pub fn projected_overrun(
+    &self,
+    period: &BudgetPeriod,
+    as_of: &TimePoint,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, FinanceError> {
+    let overrun =
+      self.projected_spend(period, as_of, rounding_strategy)? - self.allowance_for(period)?;
+    if overrun.is_positive() {
+      Ok(overrun)
+    } else {
+      Ok(Money::zero(self.currency))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+  use rust_decimal::RoundingStrategy;
+
+  use crate::finance::{Budget, BudgetPeriod, FinanceError};
+  use crate::money::Money;
+  use crate::time::{CalendarYearMonth, Duration, TimePoint};
+
+  fn yen(amount: i64) -> Money {
+    Money::from((amount, CurrencyCode::JPY))
+  }
+
+  fn at(month: u32, day: u32) -> TimePoint {
+    TimePoint::at_ymd_hms_milli_utc(2024, month, day, 0, 0, 0, 0)
+  }
+
+  fn budget() -> Budget {
+    let mut budget = Budget::new(CurrencyCode::JPY);
+    budget
+      .allocate(BudgetPeriod::month(2024, 4), yen(30_000), true)
+      .unwrap();
+    budget
+      .allocate(BudgetPeriod::month(2024, 5), yen(30_000), false)
+      .unwrap();
+    budget
+      .allocate(BudgetPeriod::month(2024, 6), yen(30_000), false)
+      .unwrap();
+    budget
+      .allocate(BudgetPeriod::quarter(2024, 2), yen(80_000), false)
+      .unwrap();
+    for (month, day, amount) in [
+      (4, 3, 10_000),
+      (4, 20, 15_000),
+      (5, 1, 9_000),
+      (5, 10, 6_000),
+    ]
+    .iter()
+    {
+      budget.record_spend(at(*month, *day), yen(*amount)).unwrap();
+    }
+    budget
+  }
+
+  #[test]
+  fn test01_period() {
+    let march = BudgetPeriod::from(CalendarYearMonth::from((2024, 3)));
+    assert_eq!(march.next(), BudgetPeriod::month(2024, 4));
+    assert_eq!(
+      BudgetPeriod::month(2024, 1).previous(),
+      BudgetPeriod::month(2023, 12)
+    );
+    assert_eq!(
+      BudgetPeriod::quarter_of(&CalendarYearMonth::from((2024, 11))),
+      BudgetPeriod::quarter(2024, 4)
+    );
+    assert_eq!(
+      BudgetPeriod::quarter(2024, 4).next(),
+      BudgetPeriod::quarter(2025, 1)
+    );
+    assert_eq!(
+      march.length().in_base_units(),
+      Duration::days(31).in_base_units()
+    );
+    assert!(BudgetPeriod::quarter(2024, 2).includes(&at(6, 30)));
+    assert!(!BudgetPeriod::quarter(2024, 2).includes(&at(7, 1)));
+  }
+
+  #[test]
+  fn test02_remaining_and_rollover() {
+    let budget = budget();
+    let april = BudgetPeriod::month(2024, 4);
+    let may = BudgetPeriod::month(2024, 5);
+    let june = BudgetPeriod::month(2024, 6);
+    assert_eq!(budget.spent_in(&april), yen(25_000));
+    assert_eq!(budget.remaining(&april), Ok(yen(5_000)));
+    assert_eq!(budget.allowance_for(&may), Ok(yen(35_000)));
+    assert_eq!(budget.remaining(&may), Ok(yen(20_000)));
+    assert_eq!(budget.allowance_for(&june), Ok(yen(30_000)));
+    assert_eq!(
+      budget.remaining(&BudgetPeriod::quarter(2024, 2)),
+      Ok(yen(40_000))
+    );
+    assert_eq!(
+      budget.remaining(&BudgetPeriod::month(2024, 7)),
+      Err(FinanceError::PeriodNotBudgetedError)
+    );
+  }
+
+  #[test]
+  fn test03_burn_rate_and_projection() {
+    let budget = budget();
+    let may = BudgetPeriod::month(2024, 5);
+    let rate = budget.burn_rate(&may, &at(5, 11)).unwrap();
+    assert_eq!(
+      rate.over(&Duration::days(1), RoundingStrategy::ToZero),
+      Ok(yen(1_500))
+    );
+    assert_eq!(
+      budget.projected_spend(&may, &at(5, 11), RoundingStrategy::ToZero),
+      Ok(yen(46_500))
+    );
+    assert_eq!(
+      budget.projected_overrun(&may, &at(5, 11), RoundingStrategy::ToZero),
+      Ok(yen(11_500))
+    );
+    assert_eq!(
+      budget.projected_overrun(&may, &at(5, 31), RoundingStrategy::ToZero),
+      Ok(yen(0))
+    );
+    assert_eq!(
+      budget.burn_rate(&may, &at(5, 1)),
+      Err(FinanceError::PeriodNotStartedError)
+    );
+  }
+
+  #[test]
+  fn test04_errors() {
+    let mut budget = budget();
+    assert_eq!(
+      budget.allocate(BudgetPeriod::month(2024, 4), yen(1), false),
+      Err(FinanceError::DuplicatePeriodError)
+    );
+    assert_eq!(
+      budget.record_spend(at(4, 1), Money::dollars_i32(1)),
+      Err(FinanceError::NotSameCurrencyError)
+    );
+  }
+}
@@ -0,0 +1,260 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::finance::{Compounding, DayCountConvention, FinanceError, InterestRate};
+use crate::money::Money;
+use crate::time::{CalendarDate, TimePoint};
+use crate::util::Percentage;
+
+/// 日付つきのキャッシュフロー。受取は正、支払は負の金額で表す。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CashFlow {
+  pub date: CalendarDate,
+  pub amount: Money,
+}
+
+impl From<(CalendarDate, Money)> for CashFlow {
+  fn from((date, amount): (CalendarDate, Money)) -> Self {
+    Self::new(date, amount)
+  }
+}
+
+impl From<(TimePoint, Money)> for CashFlow {
+  fn from((time_point, amount): (TimePoint, Money)) -> Self {
+    Self::new(time_point.into_calendar_date_utc(), amount)
+  }
+}
+
+impl CashFlow {
+  pub fn new(date: CalendarDate, amount: Money) -> Self {
+    Self { date, amount }
+  }
+}
+
+/// 同一通貨のキャッシュフローの系列。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CashFlows(Vec<CashFlow>);
+
+impl CashFlows {
+  const IRR_TOLERANCE: Decimal = Decimal::from_parts(1, 0, 0, false, 12);
+  const IRR_MAX_ITERATIONS: usize = 200;
+
+  /// コンストラクタ。
+  ///
+  /// キャッシュフローが空の場合、または通貨が混在している場合はエラーを返す。
+  pub fn new(values: Vec<CashFlow>) -> Result<Self, FinanceError> {
+    let first = values.first().ok_or(FinanceError::EmptyCashFlowsError)?;
+    if values
+      .iter()
+      .any(|e| e.amount.currency != first.amount.currency)
+    {
+      return Err(FinanceError::NotSameCurrencyError);
+    }
+    Ok(Self(values))
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, CashFlow> {
+    self.0.iter()
+  }
+
+  fn first_date(&self) -> &CalendarDate {
+    self
+      .0
+      .iter()
+      .map(|e| &e.date)
+      .fold(&self.0[0].date, |a, b| if b.is_before(a) { b } else { a })
+  }
+
+  /// 基準日 `valuation_date` における正味現在価値を返す。
+  ///
+  /// 割引の計算が `Decimal` の範囲を超える場合はエラーを返す。
+  pub fn net_present_value(
+    &self,
+    rate: &InterestRate,
+    valuation_date: &CalendarDate,
+    day_count: DayCountConvention,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, FinanceError> {
+    let currency = self.0[0].amount.currency;
+    let amount = self.discounted_sum(rate, valuation_date, day_count)?;
+    Ok(Money { amount, currency }.rounded(rounding_strategy))
+  }
+
+  /// 内部収益率（年複利）を返す。
+  ///
+  /// 正と負の両方のキャッシュフローが含まれない場合、または解が求まらない場合はエラーを返す。
+  /// 探索範囲の端で割引の計算が `Decimal` の範囲を超える場合は、範囲を狭めて探索する。
+  pub fn internal_rate_of_return(
+    &self,
+    day_count: DayCountConvention,
+  ) -> Result<Percentage, FinanceError> {
+    if !self.0.iter().any(|e| e.amount.is_positive())
+      || !self.0.iter().any(|e| e.amount.is_negative())
+    {
+      return Err(FinanceError::NoSignChangeError);
+    }
+    let valuation_date = self.first_date().clone();
+    let npv = |rate: Decimal| {
+      let rate = InterestRate::new(
+        Percentage::new(rate * Decimal::ONE_HUNDRED),
+        Compounding::Annually,
+      );
+      self
+        .discounted_sum(&rate, &valuation_date, day_count)
+        .map_err(|_| FinanceError::NotConvergedError)
+    };
+
+    let mut low = Decimal::new(-99, 2);
+    let mut low_value = npv(low);
+    while low_value.is_err() && low < -Self::IRR_TOLERANCE {
+      low /= Decimal::TWO;
+      low_value = npv(low);
+    }
+    let low_value = low_value?;
+    let mut high = Decimal::ONE;
+    while low_value.is_sign_negative() == npv(high)?.is_sign_negative() {
+      if high > Decimal::from(1_000) {
+        return Err(FinanceError::NotConvergedError);
+      }
+      high *= Decimal::TWO;
+    }
+
+    for _ in 0..Self::IRR_MAX_ITERATIONS {
+      let mid = (low + high) / Decimal::TWO;
+      let mid_value = npv(mid)?;
+      if mid_value.is_zero() || (high - low) < Self::IRR_TOLERANCE {
+        return Ok(Percentage::new(mid * Decimal::ONE_HUNDRED));
+      }
+      if low_value.is_sign_negative() != mid_value.is_sign_negative() {
+        high = mid;
+      } else {
+        low = mid;
+      }
+    }
+    Err(FinanceError::NotConvergedError)
+  }
+
+  fn discounted_sum(
+    &self,
+    rate: &InterestRate,
+    valuation_date: &CalendarDate,
+    day_count: DayCountConvention,
+  ) -> Result<Decimal, FinanceError> {
+    self.0.iter().try_fold(Decimal::ZERO, |acc, e| {
+      let years = day_count.year_fraction(valuation_date, &e.date);
+      let discounted = rate.discount(e.amount.amount, years)?;
+      acc
+        .checked_add(discounted)
+        .ok_or(FinanceError::OverflowError)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::{Decimal, RoundingStrategy};
+
+  use crate::finance::{
+    CashFlow, CashFlows, Compounding, DayCountConvention, FinanceError, InterestRate,
+  };
+  use crate::money::Money;
+  use crate::time::{CalendarDate, TimePoint};
+  use crate::util::Percentage;
+
+  fn flow(year: i32, month: u32, day: u32, amount: i32) -> CashFlow {
+    CashFlow::new(
+      CalendarDate::from((year, month, day)),
+      Money::dollars_i32(amount),
+    )
+  }
+
+  fn xirr_example() -> CashFlows {
+    CashFlows::new(vec![
+      flow(2008, 1, 1, -10000),
+      flow(2008, 3, 1, 2750),
+      flow(2008, 10, 30, 4250),
+      flow(2009, 2, 15, 3250),
+      flow(2009, 4, 1, 2750),
+    ])
+    .unwrap()
+  }
+
+  #[test]
+  fn test01_net_present_value() {
+    let rate = InterestRate::new(Percentage::from_str("9%").unwrap(), Compounding::Annually);
+    let npv = xirr_example().net_present_value(
+      &rate,
+      &CalendarDate::from((2008, 1, 1)),
+      DayCountConvention::Actual365Fixed,
+      RoundingStrategy::MidpointAwayFromZero,
+    );
+    assert_eq!(npv, Ok(Money::from(("2086.65", CurrencyCode::USD))));
+  }
+
+  #[test]
+  fn test02_internal_rate_of_return() {
+    let irr = xirr_example()
+      .internal_rate_of_return(DayCountConvention::Actual365Fixed)
+      .unwrap();
+    assert_eq!(
+      irr.to_decimal().round_dp(4),
+      Decimal::from_str("37.3363").unwrap()
+    );
+
+    let one_year = CashFlows::new(vec![
+      CashFlow::from((
+        TimePoint::at_ymd_hms_milli_utc(2021, 1, 1, 0, 0, 0, 0),
+        Money::dollars_i32(-1000),
+      )),
+      flow(2022, 1, 1, 1100),
+    ])
+    .unwrap();
+    assert_eq!(
+      one_year
+        .internal_rate_of_return(DayCountConvention::Actual365Fixed)
+        .unwrap()
+        .to_decimal()
+        .round_dp(6),
+      Decimal::from(10)
+    );
+  }
+
+  #[test]
+  fn test03_errors() {
+    assert_eq!(
+      CashFlows::new(vec![]),
+      Err(FinanceError::EmptyCashFlowsError)
+    );
+    assert_eq!(
+      CashFlows::new(vec![
+        flow(2020, 1, 1, -1),
+        CashFlow::new(
+          CalendarDate::from((2021, 1, 1)),
+          Money::from((1u32, CurrencyCode::JPY))
+        ),
+      ]),
+      Err(FinanceError::NotSameCurrencyError)
+    );
+    assert_eq!(
+      CashFlows::new(vec![flow(2020, 1, 1, 1), flow(2021, 1, 1, 1)])
+        .unwrap()
+        .internal_rate_of_return(DayCountConvention::Actual365Fixed),
+      Err(FinanceError::NoSignChangeError)
+    );
+  }
+
+  #[test]
+  fn test04_long_horizon() {
+    // 30年間で5倍になる場合、下限 -99% での割引は Decimal の範囲を超える
+    let flows = CashFlows::new(vec![flow(2000, 1, 1, -1000), flow(2030, 1, 1, 5000)]).unwrap();
+    let irr = flows
+      .internal_rate_of_return(DayCountConvention::Actual365Fixed)
+      .unwrap();
+    assert_eq!(
+      irr.to_decimal().round_dp(2),
+      Decimal::from_str("5.51").unwrap()
+    );
+  }
+}
@@ -0,0 +1,122 @@
+use rust_decimal::Decimal;
+
+use crate::time::{CalendarDate, Duration, TimeUnit};
+
+/// 日数計算規約。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DayCountConvention {
+  /// 実日数 / 360
+  Actual360,
+  /// 実日数 / 365
+  Actual365Fixed,
+  /// 30/360（US Bond Basis）
+  Thirty360,
+}
+
+impl DayCountConvention {
+  /// `start` から `end` までの日数を、この規約で数える。
+  pub fn day_count(&self, start: &CalendarDate, end: &CalendarDate) -> i64 {
+    match self {
+      DayCountConvention::Actual360 | DayCountConvention::Actual365Fixed => {
+        Self::actual_days(start, end)
+      }
+      DayCountConvention::Thirty360 => {
+        let (y1, m1, mut d1) = Self::ymd(start);
+        let (y2, m2, mut d2) = Self::ymd(end);
+        if d1 == 31 {
+          d1 = 30;
+        }
+        if d2 == 31 && d1 == 30 {
+          d2 = 30;
+        }
+        360 * (y2 - y1) + 30 * (m2 - m1) + (d2 - d1)
+      }
+    }
+  }
+
+  /// 1年あたりの日数。
+  pub fn days_per_year(&self) -> i64 {
+    match self {
+      DayCountConvention::Actual360 | DayCountConvention::Thirty360 => 360,
+      DayCountConvention::Actual365Fixed => 365,
+    }
+  }
+
+  /// `start` から `end` までの期間を年単位で返す。
+  pub fn year_fraction(&self, start: &CalendarDate, end: &CalendarDate) -> Decimal {
+    Decimal::from(self.day_count(start, end)) / Decimal::from(self.days_per_year())
+  }
+
+  /// 期間 `duration` を年単位で返す。
+  ///
+  /// 月単位の期間は12ヶ月を1年とし、ミリ秒単位の期間はこの規約の1年あたりの日数で割る。
+  pub fn year_fraction_of(&self, duration: &Duration) -> Decimal {
+    let base_units = Decimal::from(duration.in_base_units());
+    if duration.as_unit().is_convertible_to_milliseconds() {
+      base_units
+        / Decimal::from(Duration::days(1).in_base_units())
+        / Decimal::from(self.days_per_year())
+    } else {
+      base_units / Decimal::from(Duration::new(1, TimeUnit::YEAR).in_base_units())
+    }
+  }
+
+  fn actual_days(start: &CalendarDate, end: &CalendarDate) -> i64 {
+    (end.to_date_time_on_midnight_at_utc() - start.to_date_time_on_midnight_at_utc()).num_days()
+  }
+
+  fn ymd(date: &CalendarDate) -> (i64, i64, i64) {
+    (
+      date.as_year_month().to_year() as i64,
+      date.as_year_month().to_month_u32() as i64,
+      date.as_day().to_u32() as i64,
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rust_decimal::Decimal;
+
+  use crate::finance::DayCountConvention;
+  use crate::time::{CalendarDate, Duration};
+
+  #[test]
+  fn test01_day_count() {
+    let start = CalendarDate::from((2024, 1, 31));
+    let end = CalendarDate::from((2024, 3, 31));
+    assert_eq!(DayCountConvention::Actual360.day_count(&start, &end), 60);
+    assert_eq!(
+      DayCountConvention::Actual365Fixed.day_count(&start, &end),
+      60
+    );
+    assert_eq!(DayCountConvention::Thirty360.day_count(&start, &end), 60);
+
+    let start = CalendarDate::from((2023, 2, 28));
+    let end = CalendarDate::from((2023, 3, 31));
+    assert_eq!(DayCountConvention::Actual360.day_count(&start, &end), 31);
+    assert_eq!(DayCountConvention::Thirty360.day_count(&start, &end), 33);
+  }
+
+  #[test]
+  fn test02_year_fraction() {
+    let start = CalendarDate::from((2023, 1, 1));
+    let end = CalendarDate::from((2024, 1, 1));
+    assert_eq!(
+      DayCountConvention::Actual365Fixed.year_fraction(&start, &end),
+      Decimal::ONE
+    );
+    assert_eq!(
+      DayCountConvention::Actual360.year_fraction(&start, &end),
+      Decimal::from(365) / Decimal::from(360)
+    );
+    assert_eq!(
+      DayCountConvention::Thirty360.year_fraction_of(&Duration::months(18)),
+      Decimal::new(15, 1)
+    );
+    assert_eq!(
+      DayCountConvention::Actual360.year_fraction_of(&Duration::days(90)),
+      Decimal::new(25, 2)
+    );
+  }
+}
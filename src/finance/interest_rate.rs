@@ -0,0 +1,294 @@
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, MathematicalOps, RoundingStrategy};
+
+use crate::finance::FinanceError;
+use crate::money::Money;
+use crate::util::Percentage;
+
+/// 複利計算の頻度。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Compounding {
+  /// 単利
+  Simple,
+  Annually,
+  SemiAnnually,
+  Quarterly,
+  Monthly,
+  Daily,
+  /// 連続複利
+  Continuous,
+}
+
+impl Compounding {
+  /// 1年あたりの複利計算の回数。単利・連続複利の場合は `None`。
+  pub fn periods_per_year(&self) -> Option<u32> {
+    match self {
+      Compounding::Simple | Compounding::Continuous => None,
+      Compounding::Annually => Some(1),
+      Compounding::SemiAnnually => Some(2),
+      Compounding::Quarterly => Some(4),
+      Compounding::Monthly => Some(12),
+      Compounding::Daily => Some(365),
+    }
+  }
+}
+
+/// 年利と複利計算の頻度の組。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct InterestRate {
+  annual_rate: Percentage,
+  compounding: Compounding,
+}
+
+impl InterestRate {
+  pub fn new(annual_rate: Percentage, compounding: Compounding) -> Self {
+    Self {
+      annual_rate,
+      compounding,
+    }
+  }
+
+  pub fn simple(annual_rate: Percentage) -> Self {
+    Self::new(annual_rate, Compounding::Simple)
+  }
+
+  pub fn as_annual_rate(&self) -> &Percentage {
+    &self.annual_rate
+  }
+
+  pub fn as_compounding(&self) -> &Compounding {
+    &self.compounding
+  }
+
+  /// `years` 年間で元本が何倍になるかを返す。計算が `Decimal` の範囲を超える場合は `None`。
+  pub fn growth_factor(&self, years: Decimal) -> Option<Decimal> {
+    let rate = self.annual_rate.to_factor();
+    match self.compounding {
+      Compounding::Simple => Decimal::ONE.checked_add(rate.checked_mul(years)?),
+      Compounding::Continuous => rate.checked_mul(years)?.checked_exp(),
+      _ => {
+        let n = Decimal::from(self.compounding.periods_per_year().unwrap());
+        let periods = n.checked_mul(years)?;
+        let per_period = Decimal::ONE.checked_add(rate.checked_div(n)?)?;
+        if periods.fract().is_zero() {
+          per_period.checked_powi(periods.to_i64()?)
+        } else {
+          per_period.checked_powd(periods)
+        }
+      }
+    }
+  }
+
+  /// 実効年利を返す。
+  pub fn effective_annual_rate(&self) -> Result<Percentage, FinanceError> {
+    let factor = self
+      .growth_factor(Decimal::ONE)
+      .ok_or(FinanceError::OverflowError)?;
+    (factor - Decimal::ONE)
+      .checked_mul(Decimal::ONE_HUNDRED)
+      .map(Percentage::new)
+      .ok_or(FinanceError::OverflowError)
+  }
+
+  /// 現在価値 `present` の `years` 年後の将来価値を返す。
+  pub fn future_value(
+    &self,
+    present: &Money,
+    years: Decimal,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, FinanceError> {
+    let amount = self
+      .growth_factor(years)
+      .and_then(|e| present.amount.checked_mul(e))
+      .ok_or(FinanceError::OverflowError)?;
+    Ok(
+      Money {
+        amount,
+        currency: present.currency,
+      }
+      .rounded(rounding_strategy),
+    )
+  }
+
+  /// `years` 年後の将来価値 `future` の現在価値を返す。
+  ///
+  /// 単利で `1 + 年利 × years` がゼロになる場合は `DivisionByZeroError` を返す。
+  pub fn present_value(
+    &self,
+    future: &Money,
+    years: Decimal,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, FinanceError> {
+    let amount = self.discount(future.amount, years)?;
+    Ok(
+      Money {
+        amount,
+        currency: future.currency,
+      }
+      .rounded(rounding_strategy),
+    )
+  }
+
+  /// 元本 `principal` に `years` 年間で付く利息を返す。
+  pub fn interest(
+    &self,
+    principal: &Money,
+    years: Decimal,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, FinanceError> {
+    Ok(self.future_value(principal, years, rounding_strategy)? - principal.clone())
+  }
+
+  /// `years` 年後の金額 `amount` を現在の金額に割り引く。
+  pub(crate) fn discount(&self, amount: Decimal, years: Decimal) -> Result<Decimal, FinanceError> {
+    let factor = self
+      .growth_factor(years)
+      .ok_or(FinanceError::OverflowError)?;
+    if factor.is_zero() {
+      return Err(FinanceError::DivisionByZeroError);
+    }
+    amount
+      .checked_div(factor)
+      .ok_or(FinanceError::OverflowError)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::{Decimal, RoundingStrategy};
+
+  use crate::finance::{Compounding, DayCountConvention, FinanceError, InterestRate};
+  use crate::money::Money;
+  use crate::time::Duration;
+  use crate::util::Percentage;
+
+  fn usd(s: &str) -> Money {
+    Money::from((s, CurrencyCode::USD))
+  }
+
+  fn percent(s: &str) -> Percentage {
+    Percentage::from_str(s).unwrap()
+  }
+
+  #[test]
+  fn test01_simple_interest() {
+    let rate = InterestRate::simple(percent("5%"));
+    let years = DayCountConvention::Actual360.year_fraction_of(&Duration::days(90));
+    assert_eq!(
+      rate.interest(&usd("10000"), years, RoundingStrategy::MidpointNearestEven),
+      Ok(usd("125"))
+    );
+  }
+
+  #[test]
+  fn test02_compound_interest() {
+    let principal = usd("1000");
+    let annually = InterestRate::new(percent("6%"), Compounding::Annually);
+    assert_eq!(
+      annually.future_value(
+        &principal,
+        Decimal::from(2),
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Ok(usd("1123.60"))
+    );
+
+    let monthly = InterestRate::new(percent("6%"), Compounding::Monthly);
+    assert_eq!(
+      monthly.future_value(
+        &principal,
+        Decimal::ONE,
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Ok(usd("1061.68"))
+    );
+    assert_eq!(
+      monthly
+        .effective_annual_rate()
+        .unwrap()
+        .to_decimal()
+        .round_dp(4),
+      Decimal::from_str("6.1678").unwrap()
+    );
+
+    let continuous = InterestRate::new(percent("6%"), Compounding::Continuous);
+    assert_eq!(
+      continuous.future_value(
+        &principal,
+        Decimal::ONE,
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Ok(usd("1061.84"))
+    );
+
+    let half_year = DayCountConvention::Thirty360.year_fraction_of(&Duration::months(6));
+    let quarterly = InterestRate::new(percent("8%"), Compounding::Quarterly);
+    assert_eq!(
+      quarterly.interest(
+        &principal,
+        half_year,
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Ok(usd("40.40"))
+    );
+  }
+
+  #[test]
+  fn test03_present_value() {
+    let rate = InterestRate::new(percent("5%"), Compounding::Annually);
+    assert_eq!(
+      rate.present_value(
+        &usd("1102.50"),
+        Decimal::from(2),
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Ok(usd("1000"))
+    );
+    assert_eq!(
+      rate.present_value(
+        &usd("1000"),
+        Decimal::from_str("1.5").unwrap(),
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Ok(usd("929.43"))
+    );
+  }
+
+  #[test]
+  fn test04_overflow_and_division_by_zero() {
+    let principal = usd("1000");
+    let huge = InterestRate::new(percent("1000%"), Compounding::Annually);
+    assert_eq!(huge.growth_factor(Decimal::from(100)), None);
+    assert_eq!(
+      huge.future_value(
+        &principal,
+        Decimal::from(100),
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Err(FinanceError::OverflowError)
+    );
+    let continuous = InterestRate::new(percent("1000%"), Compounding::Continuous);
+    assert_eq!(
+      continuous.future_value(
+        &principal,
+        Decimal::from(100),
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Err(FinanceError::OverflowError)
+    );
+
+    // 1 + (-50%) × 2 = 0
+    let simple = InterestRate::simple(percent("-50%"));
+    assert_eq!(
+      simple.present_value(
+        &principal,
+        Decimal::from(2),
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Err(FinanceError::DivisionByZeroError)
+    );
+  }
+}
@@ -0,0 +1,26 @@
+mod amortization;
+mod budget;
+mod cash_flow;
+mod day_count_convention;
+mod interest_rate;
+
+pub use self::amortization::*;
+pub use self::budget::*;
+pub use self::cash_flow::*;
+pub use self::day_count_convention::*;
+pub use self::interest_rate::*;
+
+#[derive(Debug, PartialEq)]
+pub enum FinanceError {
+  NotSameCurrencyError,
+  EmptyCashFlowsError,
+  NoSignChangeError,
+  NotConvergedError,
+  /// 計算結果が `Decimal` の範囲を超えた。
+  OverflowError,
+  DivisionByZeroError,
+  InvalidTermError,
+  DuplicatePeriodError,
+  PeriodNotBudgetedError,
+  PeriodNotStartedError,
+}
@@ -4,27 +4,35 @@ use crate::intervals::{IntervalLimit, LimitValue};
 use rust_fp_categories::Empty;
 use std::fmt::Debug;
 
+#[derive(Debug, PartialEq)]
+pub enum IntervalError {
+  /// 下限が上限より大きい。
+  NotOrderedError,
+  /// 値を区間の限界値と比較できない（例: 通貨の異なる金額）。
+  IncomparableError,
+}
+
 #[derive(Debug, Clone)]
 pub struct Interval<T> {
   lower: IntervalLimit<T>,
   upper: IntervalLimit<T>,
 }
 
-impl<T: Debug + Default + Clone + PartialOrd> Default for Interval<T> {
+impl<T: Debug + Clone + PartialOrd> Default for Interval<T> {
   fn default() -> Self {
     Interval::single_element(LimitValue::default())
   }
 }
 
-impl<T: Debug + Default + Clone + PartialEq + PartialOrd> PartialEq for Interval<T> {
+impl<T: Debug + Clone + PartialEq + PartialOrd> PartialEq for Interval<T> {
   fn eq(&self, other: &Self) -> bool {
     self.lower.partial_cmp(&other.lower) == Some(Ordering::Equal)
       && self.upper.partial_cmp(&other.upper) == Some(Ordering::Equal)
   }
 }
 
-impl<T: Debug + Default + Clone + PartialEq + PartialOrd>
-  From<(LimitValue<T>, bool, LimitValue<T>, bool)> for Interval<T>
+impl<T: Debug + Clone + PartialEq + PartialOrd> From<(LimitValue<T>, bool, LimitValue<T>, bool)>
+  for Interval<T>
 {
   fn from(
     (lower, is_lower_closed, upper, is_upper_closed): (LimitValue<T>, bool, LimitValue<T>, bool),
@@ -44,21 +52,42 @@ impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Empty for Interval<T>
   }
 
   fn is_empty(&self) -> bool {
-    match (self.upper_limit(), self.lower_limit()) {
-      (LimitValue::Limitless, LimitValue::Limitless) => false,
-      (..) => self.is_open() && self.upper_limit() == self.lower_limit(),
-    }
+    self.has_no_elements()
   }
 }
 
-impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Interval<T> {
-  fn check_lower_is_less_than_or_equal_upper(lower: &IntervalLimit<T>, upper: &IntervalLimit<T>) {
-    if !(lower.lower && upper.is_upper() && lower.partial_cmp(upper) == Some(Ordering::Less)) {
-      panic!("{:?} is not before or equal to {:?}", lower, upper)
+impl<T: Debug + Clone + PartialEq + PartialOrd> Interval<T> {
+  fn check_lower_is_less_than_or_equal_upper(
+    lower: &IntervalLimit<T>,
+    upper: &IntervalLimit<T>,
+  ) -> Result<(), IntervalError> {
+    if !(lower.lower && upper.is_upper()) {
+      return Err(IntervalError::NotOrderedError);
+    }
+    match lower.partial_cmp(upper) {
+      Some(Ordering::Less) | Some(Ordering::Equal) => Ok(()),
+      Some(Ordering::Greater) => Err(IntervalError::NotOrderedError),
+      None => Err(IntervalError::IncomparableError),
     }
   }
+
+  /// コンストラクタ。
+  ///
+  /// 下限が上限より大きい場合、または両端を比較できない場合はパニックする。
   pub fn new(lower: IntervalLimit<T>, upper: IntervalLimit<T>) -> Self {
-    Self::check_lower_is_less_than_or_equal_upper(&lower, &upper);
+    if Self::check_lower_is_less_than_or_equal_upper(&lower, &upper).is_err() {
+      panic!("{:?} is not before or equal to {:?}", lower, upper)
+    }
+    Self::normalized(lower, upper)
+  }
+
+  /// [Interval::new] と異なり、下限が上限より大きい場合、または両端を比較できない場合はエラーを返す。
+  pub fn try_new(lower: IntervalLimit<T>, upper: IntervalLimit<T>) -> Result<Self, IntervalError> {
+    Self::check_lower_is_less_than_or_equal_upper(&lower, &upper)?;
+    Ok(Self::normalized(lower, upper))
+  }
+
+  fn normalized(lower: IntervalLimit<T>, upper: IntervalLimit<T>) -> Self {
     let (new_lower, new_upper) = if !upper.infinity()
       && !lower.infinity()
       && upper.value == lower.value
@@ -109,6 +138,19 @@ impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Interval<T> {
     Self::from((lower, lower_included, upper, upper_included))
   }
 
+  /// [Interval::over] と異なり、下限が上限より大きい場合、または両端を比較できない場合はエラーを返す。
+  pub fn try_over(
+    lower: LimitValue<T>,
+    lower_included: bool,
+    upper: LimitValue<T>,
+    upper_included: bool,
+  ) -> Result<Self, IntervalError> {
+    Self::try_new(
+      IntervalLimit::lower(lower_included, lower),
+      IntervalLimit::upper(upper_included, upper),
+    )
+  }
+
   pub fn single_element(element: LimitValue<T>) -> Self {
     Self::closed(element.clone(), element)
   }
@@ -125,7 +167,14 @@ impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Interval<T> {
     if !self.has_upper_limit() {
       false
     } else {
-      self.upper_limit() == self.lower_limit() && !self.is_empty()
+      self.upper_limit() == self.lower_limit() && !self.has_no_elements()
+    }
+  }
+
+  fn has_no_elements(&self) -> bool {
+    match (self.upper_limit(), self.lower_limit()) {
+      (LimitValue::Limitless, LimitValue::Limitless) => false,
+      (..) => self.is_open() && self.upper_limit() == self.lower_limit(),
     }
   }
 
@@ -148,8 +197,24 @@ impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Interval<T> {
     Interval::from((lower, lower_closed, upper, upper_closed))
   }
 
-  fn includes(&self, value: &LimitValue<T>) -> bool {
-    !self.is_below(value) && !self.is_above(value)
+  /// 値 `value` が区間に含まれるかを返す。
+  ///
+  /// 区間の限界値と比較できない値は含まれないとする。
+  pub fn includes(&self, value: &LimitValue<T>) -> bool {
+    self.try_includes(value).unwrap_or(false)
+  }
+
+  /// [Interval::includes] と異なり、区間の限界値と比較できない値の場合はエラーを返す。
+  pub fn try_includes(&self, value: &LimitValue<T>) -> Result<bool, IntervalError> {
+    let comparable = |limit: &LimitValue<T>| match limit {
+      LimitValue::Limit(_) => limit.partial_cmp(value).is_some(),
+      LimitValue::Limitless => true,
+    };
+    if comparable(self.lower_limit()) && comparable(self.upper_limit()) {
+      Ok(!self.is_below(value) && !self.is_above(value))
+    } else {
+      Err(IntervalError::IncomparableError)
+    }
   }
 
   fn greater_of_lower_included_in_intersection(&self, other: &Self) -> bool {
@@ -205,7 +270,32 @@ impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Interval<T> {
     }
   }
 
+  /// 区間 `other` と共通部分を持つかを返す。
+  ///
+  /// 限界値どうしを比較できない区間とは共通部分を持たないとする。
   pub fn intersects(&self, other: &Self) -> bool {
+    self.try_intersects(other).unwrap_or(false)
+  }
+
+  /// [Interval::intersects] と異なり、限界値どうしを比較できない場合はエラーを返す。
+  pub fn try_intersects(&self, other: &Self) -> Result<bool, IntervalError> {
+    let limits = [
+      self.lower_limit(),
+      self.upper_limit(),
+      other.lower_limit(),
+      other.upper_limit(),
+    ];
+    if limits
+      .iter()
+      .all(|a| limits.iter().all(|b| a.partial_cmp(b).is_some()))
+    {
+      Ok(self.intersects_comparable(other))
+    } else {
+      Err(IntervalError::IncomparableError)
+    }
+  }
+
+  fn intersects_comparable(&self, other: &Self) -> bool {
     if self.equal_both_limitless(self.upper_limit(), other.upper_limit()) {
       true
     } else {
@@ -247,15 +337,15 @@ impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Interval<T> {
   }
 
   pub fn includes_upper_limit(&self) -> bool {
-    self.lower.closed
+    self.upper.closed
   }
 
   pub fn includes_lower_limit(&self) -> bool {
-    self.upper.closed
+    self.lower.closed
   }
 
   pub fn is_below(&self, value: &LimitValue<T>) -> bool {
-    if self.has_upper_limit() {
+    if !self.has_upper_limit() {
       false
     } else {
       *self.upper_limit() < *value || *self.upper_limit() == *value && !self.includes_upper_limit()
@@ -263,7 +353,7 @@ impl<T: Debug + Default + Clone + PartialEq + PartialOrd> Interval<T> {
   }
 
   pub fn is_above(&self, value: &LimitValue<T>) -> bool {
-    if self.has_lower_limit() {
+    if !self.has_lower_limit() {
       false
     } else {
       *self.lower_limit() > *value || *self.lower_limit() == *value && !self.includes_lower_limit()
@@ -8,13 +8,13 @@ pub struct IntervalLimit<T> {
   pub(crate) value: LimitValue<T>,
 }
 
-impl<T: Default + Clone + PartialOrd> Default for IntervalLimit<T> {
+impl<T: Clone + PartialOrd> Default for IntervalLimit<T> {
   fn default() -> Self {
     IntervalLimit::new(false, false, LimitValue::default())
   }
 }
 
-impl<T: Default + Clone + PartialEq + PartialOrd> PartialOrd for IntervalLimit<T> {
+impl<T: Clone + PartialEq + PartialOrd> PartialOrd for IntervalLimit<T> {
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
     match (&self.value, &other.value) {
       (LimitValue::<T>::Limitless, LimitValue::<T>::Limitless) => {
@@ -54,13 +54,13 @@ impl<T: Default + Clone + PartialEq + PartialOrd> PartialOrd for IntervalLimit<T
   }
 }
 
-impl<T: Default + Clone + PartialOrd> PartialEq for IntervalLimit<T> {
+impl<T: Clone + PartialOrd> PartialEq for IntervalLimit<T> {
   fn eq(&self, other: &Self) -> bool {
     self.partial_cmp(other).unwrap() == Ordering::Equal
   }
 }
 
-impl<T: Default + Clone + PartialEq + PartialOrd> IntervalLimit<T> {
+impl<T: Clone + PartialEq + PartialOrd> IntervalLimit<T> {
   pub fn new(closed: bool, lower: bool, value: LimitValue<T>) -> Self {
     Self {
       closed,
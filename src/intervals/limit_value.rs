@@ -1,18 +1,13 @@
 use std::cmp::Ordering;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum LimitValue<T> {
   Limit(T),
+  #[default]
   Limitless,
 }
 
-impl<T: Clone + Default> Default for LimitValue<T> {
-  fn default() -> Self {
-    LimitValue::Limitless
-  }
-}
-
-impl<T: Clone + Default + PartialOrd> PartialOrd for LimitValue<T> {
+impl<T: Clone + PartialOrd> PartialOrd for LimitValue<T> {
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
     match (self, other) {
       (LimitValue::Limit(ref value), LimitValue::Limit(ref other_value)) => {
@@ -25,7 +20,7 @@ impl<T: Clone + Default + PartialOrd> PartialOrd for LimitValue<T> {
   }
 }
 
-impl<T: Clone + Default + PartialEq + PartialOrd> PartialEq for LimitValue<T> {
+impl<T: Clone + PartialEq + PartialOrd> PartialEq for LimitValue<T> {
   fn eq(&self, other: &Self) -> bool {
     match (self, other) {
       (&LimitValue::Limitless, &LimitValue::Limitless) => true,
@@ -35,9 +30,9 @@ impl<T: Clone + Default + PartialEq + PartialOrd> PartialEq for LimitValue<T> {
   }
 }
 
-impl<T: Clone + Default + PartialOrd> Eq for LimitValue<T> {}
+impl<T: Clone + PartialOrd> Eq for LimitValue<T> {}
 
-impl<T: Clone + Default> LimitValue<T> {
+impl<T: Clone> LimitValue<T> {
   pub fn to_value(&self) -> &T {
     match self {
       LimitValue::Limit(ref t) => t,
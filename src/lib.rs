@@ -3,7 +3,13 @@
 #![feature(once_cell)]
 #![allow(dead_code)]
 
+pub mod accounting;
+pub mod finance;
 pub mod intervals;
 pub mod money;
+pub mod pricing;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod tax;
 pub mod time;
 pub mod util;
@@ -2,6 +2,7 @@ use crate::money::Money;
 use std::ops::Neg;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Allotment<T> {
   pub entity: T,
   pub amount: Money,
@@ -1,19 +1,23 @@
-use std::collections::hash_set::{Iter, IntoIter};
-use std::collections::HashSet;
+use std::collections::hash_map::{Entry, IntoValues, Values};
+use std::collections::HashMap;
 use std::hash::Hash;
+use std::iter::FromIterator;
 
 use rust_fp_categories::Empty;
 use rust_fp_categories::Semigroup;
 
-use crate::money::Allotment;
+use crate::money::{Allotment, MoneyError};
 use rust_fp_categories::Monoid;
 
-#[derive(Debug, Clone)]
-pub struct Allotments<T>(pub(crate) HashSet<Allotment<T>>);
+/// エンティティをキーとした [Allotment] の集合。
+///
+/// 同じエンティティへの割り当ては1つにまとめられ、金額は合算される。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Allotments<T: Eq + Hash>(pub(crate) HashMap<T, Allotment<T>>);
 
-impl<T> Empty for Allotments<T> {
+impl<T: Eq + Hash> Empty for Allotments<T> {
   fn empty() -> Self {
-    Allotments(HashSet::new())
+    Allotments(HashMap::new())
   }
 
   fn is_empty(&self) -> bool {
@@ -21,60 +25,187 @@ impl<T> Empty for Allotments<T> {
   }
 }
 
+/// 同じエンティティに通貨の異なる割り当てがある場合はパニックする。[Allotments::try_combine] はエラーを返す。
 impl<T: Clone + Eq + Hash> Semigroup for Allotments<T> {
   fn combine(self, other: Self) -> Self {
-    let mut s = self.0;
-    for e in other.0 {
-      s.insert(e);
-    }
-    Allotments::new(s)
+    self
+      .try_combine(other)
+      .unwrap_or_else(|err| panic!("{:?}", err))
   }
 }
 
 impl<T: Clone + Eq + Hash> Monoid for Allotments<T> {}
 
+/// 同じエンティティに通貨の異なる割り当てがある場合はエラーを返す。
+impl<T: Clone + Eq + Hash> FromIterator<Allotment<T>> for Result<Allotments<T>, MoneyError> {
+  fn from_iter<I: IntoIterator<Item = Allotment<T>>>(iter: I) -> Self {
+    let mut s = Allotments::empty();
+    for e in iter {
+      s.insert(e)?;
+    }
+    Ok(s)
+  }
+}
+
 impl<T: Clone + Eq + Hash> Allotments<T> {
-  pub fn new(values: HashSet<Allotment<T>>) -> Self {
-    Self(values)
+  /// コンストラクタ。同じエンティティに通貨の異なる割り当てがある場合はエラーを返す。
+  pub fn new<I>(values: I) -> Result<Self, MoneyError>
+  where
+    I: IntoIterator<Item = Allotment<T>>,
+  {
+    values.into_iter().collect()
+  }
+
+  /// 割り当てを追加する。同じエンティティへの割り当てが既にある場合は金額を合算する。
+  ///
+  /// 既にある割り当てと通貨が異なる場合は追加せずにエラーを返す。
+  pub fn insert(&mut self, allotment: Allotment<T>) -> Result<(), MoneyError> {
+    match self.0.entry(allotment.entity.clone()) {
+      Entry::Occupied(mut e) => {
+        let amount = e.get().amount.clone().add(allotment.amount)?;
+        e.get_mut().amount = amount;
+      }
+      Entry::Vacant(e) => {
+        e.insert(allotment);
+      }
+    }
+    Ok(())
+  }
+
+  /// 2つの集合を合わせる。同じエンティティに通貨の異なる割り当てがある場合はエラーを返す。
+  pub fn try_combine(self, other: Self) -> Result<Self, MoneyError> {
+    let mut s = self;
+    for e in other {
+      s.insert(e)?;
+    }
+    Ok(s)
+  }
+
+  pub fn get(&self, entity: &T) -> Option<&Allotment<T>> {
+    self.0.get(entity)
+  }
+
+  pub fn contains(&self, entity: &T) -> bool {
+    self.0.contains_key(entity)
+  }
+
+  pub fn remove(&mut self, entity: &T) -> Option<Allotment<T>> {
+    self.0.remove(entity)
   }
 
   pub fn negated(self) -> Self {
-    let values = self
-      .0
-      .into_iter()
-      .map(|e| e.negated())
-      .collect::<HashSet<_>>();
-    Self::new(values)
+    Self(self.0.into_iter().map(|(k, v)| (k, v.negated())).collect())
   }
 
   pub fn filter<F>(self, f: F) -> Self
   where
     F: Fn(&Allotment<T>) -> bool,
   {
-    let values = self.0.into_iter().filter(|e| f(e)).collect::<HashSet<_>>();
-    Self(values)
+    let mut s = self;
+    s.retain(f);
+    s
+  }
+
+  pub fn find<F>(&self, f: F) -> Option<&Allotment<T>>
+  where
+    F: Fn(&Allotment<T>) -> bool,
+  {
+    self.iter().find(|e| f(e))
   }
 
-  pub fn find<F>(self, f: F) -> Option<Allotment<T>>
+  pub fn retain<F>(&mut self, f: F)
   where
     F: Fn(&Allotment<T>) -> bool,
   {
-    self.0.into_iter().find(|e| f(e))
+    self.0.retain(|_, e| f(e))
   }
 
-  pub fn iter(&self) -> Iter<Allotment<T>> {
-    self.0.iter()
+  pub fn iter(&self) -> Values<'_, T, Allotment<T>> {
+    self.0.values()
   }
 }
 
-impl<T> std::iter::IntoIterator for Allotments<T> {
+impl<T: Eq + Hash> std::iter::IntoIterator for Allotments<T> {
   type Item = Allotment<T>;
-  type IntoIter = IntoIter<Allotment<T>>;
+  type IntoIter = IntoValues<T, Allotment<T>>;
 
   fn into_iter(self) -> Self::IntoIter {
-    self.0.into_iter()
+    self.0.into_values()
   }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+  use iso_4217::CurrencyCode;
+  use rust_fp_categories::{Empty, Semigroup};
+
+  use crate::money::{Allotment, Allotments, Money, MoneyError};
+
+  #[test]
+  fn test01_same_entity_is_summed() {
+    let allotments = Allotments::new(vec![
+      Allotment::new("Joe", Money::dollars_i32(10)),
+      Allotment::new("Mary", Money::dollars_i32(20)),
+      Allotment::new("Joe", Money::dollars_i32(5)),
+    ])
+    .unwrap();
+    assert_eq!(allotments.iter().count(), 2);
+    assert_eq!(
+      allotments.get(&"Joe"),
+      Some(&Allotment::new("Joe", Money::dollars_i32(15)))
+    );
+    assert_eq!(allotments.get(&"Jill"), None);
+  }
+
+  #[test]
+  fn test02_combine() {
+    let a = Allotments::new(vec![Allotment::new("Joe", Money::dollars_i32(10))]).unwrap();
+    let b = Allotments::new(vec![
+      Allotment::new("Joe", Money::dollars_i32(-10)),
+      Allotment::new("Jill", Money::dollars_i32(3)),
+    ])
+    .unwrap();
+    let c = a.combine(b);
+    assert_eq!(
+      c.get(&"Joe"),
+      Some(&Allotment::new("Joe", Money::dollars_i32(0)))
+    );
+    assert_eq!(
+      c.clone().combine(Allotments::empty()),
+      c.clone().negated().negated()
+    );
+  }
+
+  #[test]
+  fn test03_mixed_currencies_are_rejected() {
+    let mut allotments =
+      Allotments::new(vec![Allotment::new("Joe", Money::dollars_i32(10))]).unwrap();
+    assert_eq!(
+      allotments.insert(Allotment::new(
+        "Joe",
+        Money::from((500u32, CurrencyCode::JPY))
+      )),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+    assert_eq!(
+      allotments.get(&"Joe"),
+      Some(&Allotment::new("Joe", Money::dollars_i32(10)))
+    );
+    assert_eq!(
+      Allotments::new(vec![
+        Allotment::new("Joe", Money::dollars_i32(10)),
+        Allotment::new("Joe", Money::from((500u32, CurrencyCode::JPY))),
+      ]),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+    let yen = Allotments::new(vec![Allotment::new(
+      "Joe",
+      Money::from((500u32, CurrencyCode::JPY)),
+    )])
+    .unwrap();
+    assert_eq!(
+      allotments.try_combine(yen),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+  }
+}
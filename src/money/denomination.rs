@@ -0,0 +1,633 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, VecDeque};
+use std::convert::TryFrom;
+
+use iso_4217::CurrencyCode;
+use rust_decimal::Decimal;
+
+use crate::money::Money;
+
+#[derive(Debug, PartialEq)]
+pub enum DenominationError {
+  NotSameCurrencyError,
+  NegativeAmountError,
+  InsufficientTenderError,
+  /// 金種の組み合わせで表せない金額。
+  NotRepresentableError,
+  /// 手元の在庫では釣り銭を用意できない。
+  ExactChangeImpossibleError,
+  /// 額面がゼロ以下、通貨の最小単位で表せない、または通貨に最小単位がない。
+  InvalidDenominationError,
+  /// 分解に必要な計算表が大きすぎる。
+  AmountTooLargeError,
+}
+
+/// 両替問題の計算表の大きさ（金種数 × 最小単位の金額）の上限。
+const MAX_TABLE_SIZE: usize = 1 << 24;
+
+/// 紙幣か硬貨か。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum DenominationKind {
+  Note,
+  Coin,
+}
+
+/// 金種。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Denomination {
+  pub value: Money,
+  pub kind: DenominationKind,
+}
+
+impl Denomination {
+  pub fn note(value: Money) -> Self {
+    Self {
+      value,
+      kind: DenominationKind::Note,
+    }
+  }
+
+  pub fn coin(value: Money) -> Self {
+    Self {
+      value,
+      kind: DenominationKind::Coin,
+    }
+  }
+}
+
+/// 金種ごとの枚数。
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashCount {
+  currency: CurrencyCode,
+  counts: BTreeMap<Decimal, u32>,
+}
+
+impl CashCount {
+  pub fn new(currency: CurrencyCode) -> Self {
+    Self {
+      currency,
+      counts: BTreeMap::new(),
+    }
+  }
+
+  /// 額面 `value` の枚数に `count` を加えたものを返す。
+  ///
+  /// 通貨が異なる場合はエラーを返す。
+  pub fn with(self, value: Money, count: u32) -> Result<Self, DenominationError> {
+    if value.currency != self.currency {
+      return Err(DenominationError::NotSameCurrencyError);
+    }
+    Ok(self.with_amount(value.amount, count))
+  }
+
+  fn with_amount(mut self, amount: Decimal, count: u32) -> Self {
+    if count > 0 {
+      *self.counts.entry(amount.normalize()).or_insert(0) += count;
+    }
+    self
+  }
+
+  pub fn as_currency(&self) -> &CurrencyCode {
+    &self.currency
+  }
+
+  /// 額面 `value` の枚数を返す。
+  pub fn count_of(&self, value: &Money) -> u32 {
+    if value.currency != self.currency {
+      return 0;
+    }
+    self
+      .counts
+      .get(&value.amount.normalize())
+      .cloned()
+      .unwrap_or(0)
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.counts.is_empty()
+  }
+
+  /// 額面の大きい順に、額面と枚数の組を返す。
+  pub fn iter(&self) -> impl Iterator<Item = (Money, u32)> + '_ {
+    self
+      .counts
+      .iter()
+      .rev()
+      .map(move |(amount, count)| (Money::new(*amount, self.currency), *count))
+  }
+
+  /// 総枚数を返す。
+  pub fn pieces(&self) -> u32 {
+    self.counts.values().sum()
+  }
+
+  /// 合計金額を返す。
+  pub fn total(&self) -> Money {
+    let amount = self
+      .counts
+      .iter()
+      .map(|(amount, count)| *amount * Decimal::from(*count))
+      .sum();
+    Money::new(amount, self.currency)
+  }
+}
+
+/// 通貨ごとの金種表。
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenominationTable {
+  currency: CurrencyCode,
+  denominations: Vec<Denomination>,
+}
+
+impl DenominationTable {
+  /// コンストラクタ。
+  ///
+  /// 金種は額面の大きい順に並べ替える。通貨が異なる金種を含む場合、
+  /// 額面がゼロ以下か通貨の最小単位で表せない金種を含む場合はエラーを返す。
+  pub fn new(
+    currency: CurrencyCode,
+    mut denominations: Vec<Denomination>,
+  ) -> Result<Self, DenominationError> {
+    if denominations.iter().any(|e| e.value.currency != currency) {
+      return Err(DenominationError::NotSameCurrencyError);
+    }
+    let digit = currency
+      .digit()
+      .ok_or(DenominationError::InvalidDenominationError)?;
+    if denominations
+      .iter()
+      .any(|e| !e.value.is_positive() || Self::to_minor_units(e.value.amount, digit).is_none())
+    {
+      return Err(DenominationError::InvalidDenominationError);
+    }
+    denominations.sort_by_key(|e| Reverse(e.value.amount));
+    Ok(Self {
+      currency,
+      denominations,
+    })
+  }
+
+  /// 通貨 `currency` の標準的な金種表を返す。未対応の通貨の場合は `None`。
+  pub fn for_currency(currency: CurrencyCode) -> Option<Self> {
+    let (notes, coins): (&[&str], &[&str]) = match currency {
+      CurrencyCode::JPY => (
+        &["10000", "5000", "2000", "1000"],
+        &["500", "100", "50", "10", "5", "1"],
+      ),
+      CurrencyCode::USD => (
+        &["100", "50", "20", "10", "5", "2", "1"],
+        &["0.25", "0.10", "0.05", "0.01"],
+      ),
+      CurrencyCode::EUR => (
+        &["500", "200", "100", "50", "20", "10", "5"],
+        &["2", "1", "0.50", "0.20", "0.10", "0.05", "0.02", "0.01"],
+      ),
+      CurrencyCode::GBP => (
+        &["50", "20", "10", "5"],
+        &["2", "1", "0.50", "0.20", "0.10", "0.05", "0.02", "0.01"],
+      ),
+      _ => return None,
+    };
+    let denominations = notes
+      .iter()
+      .map(|e| Denomination::note(Money::from((*e, currency))))
+      .chain(
+        coins
+          .iter()
+          .map(|e| Denomination::coin(Money::from((*e, currency)))),
+      )
+      .collect();
+    Self::new(currency, denominations).ok()
+  }
+
+  pub fn as_currency(&self) -> &CurrencyCode {
+    &self.currency
+  }
+
+  pub fn denominations(&self) -> &[Denomination] {
+    &self.denominations
+  }
+
+  /// 金額 `amount` を、枚数の制限なしに、総枚数が最小になるよう金種ごとの枚数に分解する。
+  ///
+  /// 金種の組み合わせで表せない場合、または計算表が大きすぎる場合はエラーを返す。
+  pub fn breakdown(&self, amount: &Money) -> Result<CashCount, DenominationError> {
+    self.validate(amount)?;
+    self.solve(amount, |_| None, DenominationError::NotRepresentableError)
+  }
+
+  /// 金額 `amount` を、在庫 `inventory` の範囲で、総枚数が最小になるよう金種ごとの枚数に分解する。
+  ///
+  /// 総枚数が同じ分解が複数ある場合は大きい金種を多く使うものを選ぶ。在庫では支払えない場合はエラーを返す。
+  /// 最大の金種で支払える分を除いた残りの金額が大きすぎる場合（金種数 × 最小単位の金額が 2^24 を超える場合）もエラーを返す。
+  pub fn breakdown_with_inventory(
+    &self,
+    amount: &Money,
+    inventory: &CashCount,
+  ) -> Result<CashCount, DenominationError> {
+    self.validate(amount)?;
+    if inventory.currency != self.currency {
+      return Err(DenominationError::NotSameCurrencyError);
+    }
+    self.solve(
+      amount,
+      |value| Some(inventory.count_of(value)),
+      DenominationError::ExactChangeImpossibleError,
+    )
+  }
+
+  /// 代金 `price` に対して `tendered` を受け取ったときの釣り銭を、在庫 `drawer` の範囲で返す。
+  pub fn make_change(
+    &self,
+    price: &Money,
+    tendered: &Money,
+    drawer: &CashCount,
+  ) -> Result<CashCount, DenominationError> {
+    if price.currency != tendered.currency {
+      return Err(DenominationError::NotSameCurrencyError);
+    }
+    let change = tendered.clone() - price.clone();
+    if change.is_negative() {
+      return Err(DenominationError::InsufficientTenderError);
+    }
+    self.breakdown_with_inventory(&change, drawer)
+  }
+
+  fn validate(&self, amount: &Money) -> Result<(), DenominationError> {
+    if amount.currency != self.currency {
+      Err(DenominationError::NotSameCurrencyError)
+    } else if amount.is_negative() {
+      Err(DenominationError::NegativeAmountError)
+    } else {
+      Ok(())
+    }
+  }
+
+  fn to_minor_units(amount: Decimal, digit: u32) -> Option<i64> {
+    let units = amount * Decimal::from(10i64.pow(digit));
+    if units.fract().is_zero() {
+      i64::try_from(units).ok()
+    } else {
+      None
+    }
+  }
+
+  /// 金種ごとの枚数の上限を `limit_of` で求め、金額 `amount` を分解する。分解できない場合は `impossible` を返す。
+  fn solve<F>(
+    &self,
+    amount: &Money,
+    limit_of: F,
+    impossible: DenominationError,
+  ) -> Result<CashCount, DenominationError>
+  where
+    F: Fn(&Money) -> Option<u32>,
+  {
+    let digit = self
+      .currency
+      .digit()
+      .ok_or(DenominationError::InvalidDenominationError)?;
+    let amount = match Self::to_minor_units(amount.amount, digit) {
+      Some(e) => e,
+      None => return Err(impossible),
+    };
+    // 同じ額面の紙幣と硬貨は在庫を区別できないため1つにまとめ、在庫のない金種は除く
+    let mut values = self
+      .denominations
+      .iter()
+      .map(|e| e.value.clone())
+      .collect::<Vec<_>>();
+    values.dedup_by(|a, b| a.amount == b.amount);
+    let (values, limits): (Vec<_>, Vec<_>) = values
+      .into_iter()
+      .map(|e| {
+        let limit = limit_of(&e);
+        (e, limit)
+      })
+      .filter(|(_, limit)| *limit != Some(0))
+      .unzip();
+    let units = values
+      .iter()
+      .map(|e| Self::to_minor_units(e.amount, digit))
+      .collect::<Option<Vec<_>>>()
+      .ok_or(DenominationError::InvalidDenominationError)?;
+    let counts = Self::change_making(&units, &limits, amount)?.ok_or(impossible)?;
+    Ok(
+      values
+        .iter()
+        .zip(counts)
+        .fold(CashCount::new(self.currency), |acc, (value, count)| {
+          acc.with_amount(value.amount, count)
+        }),
+    )
+  }
+
+  /// 枚数に上限のある両替問題を、最小単位の金額ごとの動的計画法で解く。
+  ///
+  /// `units` は額面の大きい順に並んだ最小単位の額面、`limits` は枚数の上限（`None` は無制限）。
+  /// 総枚数が最小の分解のうち、大きい金種を多く使うものを返す。分解できない場合は `None`。
+  /// 計算量は O(金額 × 金種数)。最大の金種を必ず使う部分を先に取り除いて金額を抑え、
+  /// それでも計算表が [MAX_TABLE_SIZE] を超える場合はエラーを返す。
+  fn change_making(
+    units: &[i64],
+    limits: &[Option<u32>],
+    amount: i64,
+  ) -> Result<Option<Vec<u32>>, DenominationError> {
+    let largest = match units.first() {
+      Some(e) => *e,
+      None => return Ok(if amount == 0 { Some(vec![]) } else { None }),
+    };
+    // 在庫の合計を超える金額は払えない
+    let capacity = units
+      .iter()
+      .zip(limits)
+      .try_fold(0i64, |acc, (unit, limit)| match limit {
+        Some(e) => acc.checked_add(unit.checked_mul(i64::from(*e))?),
+        None => Some(i64::MAX),
+      })
+      .unwrap_or(i64::MAX);
+    if amount > capacity {
+      return Ok(None);
+    }
+    // 最大の金種 `largest` 以外の金種 `v` を `largest / gcd` 枚以上使う分解は、
+    // `v / gcd` 枚の `largest` に置き換えると枚数が減るため最小ではない。
+    // よって最小の分解では、それ以外の金種の合計が `bound` 未満になるか、
+    // 置き換えに使う `largest` の残りが `exchange` 枚未満になる。
+    let bound = units[1..]
+      .iter()
+      .map(|v| largest / gcd(largest, *v) * v)
+      .try_fold(0i64, |acc, e| acc.checked_add(e))
+      .ok_or(DenominationError::AmountTooLargeError)?;
+    let exchange = units[1..]
+      .iter()
+      .map(|v| v / gcd(largest, *v))
+      .max()
+      .unwrap_or(0);
+    let preset = if amount > bound {
+      ((amount - bound + largest - 1) / largest).min(amount / largest)
+    } else {
+      0
+    };
+    let preset = match limits[0] {
+      Some(limit) => preset.min((i64::from(limit) - exchange).max(0)),
+      None => preset,
+    };
+    let remaining = usize::try_from(amount - preset * largest)
+      .map_err(|_| DenominationError::AmountTooLargeError)?;
+    if units.len().saturating_mul(remaining + 1) > MAX_TABLE_SIZE {
+      return Err(DenominationError::AmountTooLargeError);
+    }
+    let mut limits = limits.to_vec();
+    limits[0] = limits[0].map(|e| e - preset as u32);
+
+    const INFINITY: i64 = i64::MAX / 2;
+    let mut best = vec![INFINITY; remaining + 1];
+    best[0] = 0;
+    // used[i][a]: 金額 a の最小の分解で金種 i を使う枚数
+    let mut used = vec![vec![0u32; remaining + 1]; units.len()];
+    for (i, (unit, limit)) in units.iter().zip(&limits).enumerate().rev() {
+      let unit = *unit as usize;
+      let limit = limit.map_or(remaining / unit, |e| (e as usize).min(remaining / unit));
+      let previous = best.clone();
+      for residue in 0..unit.min(remaining + 1) {
+        // 剰余類ごとに、直近 `limit + 1` 個の `previous[a - k * unit] + k` の最小値を単調キューで求める
+        let mut window = VecDeque::<(usize, i64)>::new();
+        for j in 0..=(remaining - residue) / unit {
+          let key = previous[residue + j * unit] - j as i64;
+          // 同じ値なら古い方（使う枚数が多い方）を残す
+          while window.back().is_some_and(|(_, e)| *e > key) {
+            window.pop_back();
+          }
+          window.push_back((j, key));
+          while window.front().is_some_and(|(k, _)| j - k > limit) {
+            window.pop_front();
+          }
+          let (k, key) = *window.front().unwrap();
+          let a = residue + j * unit;
+          if key < INFINITY / 2 {
+            best[a] = key + j as i64;
+            used[i][a] = (j - k) as u32;
+          } else {
+            best[a] = INFINITY;
+          }
+        }
+      }
+    }
+    if best[remaining] >= INFINITY / 2 {
+      return Ok(None);
+    }
+
+    let mut counts = vec![0u32; units.len()];
+    let mut a = remaining;
+    for (i, unit) in units.iter().enumerate() {
+      counts[i] = used[i][a];
+      a -= used[i][a] as usize * *unit as usize;
+    }
+    counts[0] = u32::try_from(preset)
+      .ok()
+      .and_then(|e| counts[0].checked_add(e))
+      .ok_or(DenominationError::AmountTooLargeError)?;
+    Ok(Some(counts))
+  }
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+  if b == 0 {
+    a
+  } else {
+    gcd(b, a % b)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+
+  use crate::money::{CashCount, Denomination, DenominationError, DenominationTable, Money};
+
+  fn yen(amount: u32) -> Money {
+    Money::from((amount, CurrencyCode::JPY))
+  }
+
+  fn usd(s: &str) -> Money {
+    Money::from((s, CurrencyCode::USD))
+  }
+
+  #[test]
+  fn test01_breakdown() {
+    let table = DenominationTable::for_currency(CurrencyCode::JPY).unwrap();
+    let breakdown = table.breakdown(&yen(18_768)).unwrap();
+    assert_eq!(
+      breakdown.iter().collect::<Vec<_>>(),
+      vec![
+        (yen(10_000), 1),
+        (yen(5_000), 1),
+        (yen(2_000), 1),
+        (yen(1_000), 1),
+        (yen(500), 1),
+        (yen(100), 2),
+        (yen(50), 1),
+        (yen(10), 1),
+        (yen(5), 1),
+        (yen(1), 3),
+      ]
+    );
+    assert_eq!(breakdown.total(), yen(18_768));
+    assert_eq!(breakdown.pieces(), 13);
+
+    let table = DenominationTable::for_currency(CurrencyCode::USD).unwrap();
+    let breakdown = table.breakdown(&usd("37.41")).unwrap();
+    assert_eq!(breakdown.count_of(&usd("20")), 1);
+    assert_eq!(breakdown.count_of(&usd("0.25")), 1);
+    assert_eq!(breakdown.count_of(&usd("0.10")), 1);
+    assert_eq!(breakdown.count_of(&usd("0.05")), 1);
+    assert_eq!(breakdown.count_of(&usd("0.01")), 1);
+    assert_eq!(breakdown.total(), usd("37.41"));
+  }
+
+  #[test]
+  fn test02_breakdown_with_inventory() {
+    let table = DenominationTable::new(
+      CurrencyCode::USD,
+      vec![Denomination::note(usd("20")), Denomination::note(usd("50"))],
+    )
+    .unwrap();
+    let drawer = CashCount::new(CurrencyCode::USD)
+      .with(usd("50"), 1)
+      .and_then(|e| e.with(usd("20"), 3))
+      .unwrap();
+    let breakdown = table.breakdown_with_inventory(&usd("60"), &drawer).unwrap();
+    assert_eq!(breakdown.count_of(&usd("50")), 0);
+    assert_eq!(breakdown.count_of(&usd("20")), 3);
+    assert_eq!(
+      table.breakdown_with_inventory(&usd("80"), &drawer),
+      Err(DenominationError::ExactChangeImpossibleError)
+    );
+    assert_eq!(
+      table.breakdown(&usd("30")),
+      Err(DenominationError::NotRepresentableError)
+    );
+  }
+
+  #[test]
+  fn test03_make_change() {
+    let table = DenominationTable::for_currency(CurrencyCode::JPY).unwrap();
+    let drawer = CashCount::new(CurrencyCode::JPY)
+      .with(yen(1_000), 5)
+      .and_then(|e| e.with(yen(500), 0))
+      .and_then(|e| e.with(yen(100), 10))
+      .and_then(|e| e.with(yen(10), 3))
+      .unwrap();
+    let change = table
+      .make_change(&yen(8_370), &yen(10_000), &drawer)
+      .unwrap();
+    assert_eq!(
+      change.iter().collect::<Vec<_>>(),
+      vec![(yen(1_000), 1), (yen(100), 6), (yen(10), 3)]
+    );
+
+    assert_eq!(
+      table.make_change(&yen(8_375), &yen(10_000), &drawer),
+      Err(DenominationError::ExactChangeImpossibleError)
+    );
+    assert_eq!(
+      table.make_change(&yen(8_370), &yen(5_000), &drawer),
+      Err(DenominationError::InsufficientTenderError)
+    );
+    assert_eq!(
+      table.make_change(&yen(1), &usd("5"), &drawer),
+      Err(DenominationError::NotSameCurrencyError)
+    );
+    assert!(DenominationTable::for_currency(CurrencyCode::CHF).is_none());
+  }
+
+  #[test]
+  fn test04_invalid_denominations() {
+    assert_eq!(
+      DenominationTable::new(CurrencyCode::USD, vec![Denomination::coin(usd("0"))]),
+      Err(DenominationError::InvalidDenominationError)
+    );
+    assert_eq!(
+      DenominationTable::new(CurrencyCode::USD, vec![Denomination::coin(usd("-1"))]),
+      Err(DenominationError::InvalidDenominationError)
+    );
+    assert_eq!(
+      DenominationTable::new(CurrencyCode::JPY, vec![Denomination::coin(usd("1"))]),
+      Err(DenominationError::NotSameCurrencyError)
+    );
+    assert_eq!(
+      CashCount::new(CurrencyCode::JPY).with(usd("1"), 1),
+      Err(DenominationError::NotSameCurrencyError)
+    );
+  }
+
+  #[test]
+  fn test05_minimum_pieces() {
+    // 貪欲法では 25 + 1×5 の6枚になるが、10×3 の3枚が最小
+    let table = DenominationTable::new(
+      CurrencyCode::USD,
+      vec![
+        Denomination::coin(usd("0.25")),
+        Denomination::coin(usd("0.10")),
+        Denomination::coin(usd("0.01")),
+      ],
+    )
+    .unwrap();
+    let breakdown = table.breakdown(&usd("0.30")).unwrap();
+    assert_eq!(breakdown.iter().collect::<Vec<_>>(), vec![(usd("0.10"), 3)]);
+    let breakdown = table.breakdown(&usd("1000000.30")).unwrap();
+    assert_eq!(breakdown.total(), usd("1000000.30"));
+    assert_eq!(breakdown.pieces(), 4_000_003);
+  }
+
+  #[test]
+  fn test06_large_inventory() {
+    let table = DenominationTable::for_currency(CurrencyCode::JPY).unwrap();
+    let drawer = table
+      .denominations()
+      .iter()
+      .filter(|e| e.value.amount >= yen(10).amount)
+      .try_fold(CashCount::new(CurrencyCode::JPY), |acc, e| {
+        acc.with(e.value.clone(), 500)
+      })
+      .unwrap();
+    assert_eq!(
+      table.breakdown_with_inventory(&yen(9_999), &drawer),
+      Err(DenominationError::ExactChangeImpossibleError)
+    );
+    let breakdown = table
+      .breakdown_with_inventory(&yen(9_990), &drawer)
+      .unwrap();
+    assert_eq!(breakdown.total(), yen(9_990));
+    assert_eq!(breakdown.pieces(), 13);
+
+    // 最大の金種の在庫が十分あれば、計算表は残りの金額の分だけで済む
+    let drawer = CashCount::new(CurrencyCode::JPY)
+      .with(yen(10_000), 10_000)
+      .unwrap();
+    let breakdown = table
+      .breakdown_with_inventory(&yen(50_000_000), &drawer)
+      .unwrap();
+    assert_eq!(
+      breakdown.iter().collect::<Vec<_>>(),
+      vec![(yen(10_000), 5_000)]
+    );
+    let drawer = drawer.with(yen(1), 100_000_000).unwrap();
+    let breakdown = table
+      .breakdown_with_inventory(&yen(50_000_003), &drawer)
+      .unwrap();
+    assert_eq!(
+      breakdown.iter().collect::<Vec<_>>(),
+      vec![(yen(10_000), 5_000), (yen(1), 3)]
+    );
+
+    // 最大の金種の在庫が足りず、残りの金額が大きすぎる場合
+    let drawer = CashCount::new(CurrencyCode::JPY)
+      .with(yen(10_000), 1)
+      .and_then(|e| e.with(yen(1), 100_000_000))
+      .unwrap();
+    assert_eq!(
+      table.breakdown_with_inventory(&yen(50_000_000), &drawer),
+      Err(DenominationError::AmountTooLargeError)
+    );
+  }
+}
@@ -1,10 +1,19 @@
 mod allotment;
 mod allotments;
+mod denomination;
 //noinspection RsExternalLinter
 mod money;
 mod money_fan;
+mod money_interval;
+mod money_time_rate;
+mod proration;
+mod tally;
 
 pub use self::money::*;
 pub use self::allotment::*;
 pub use self::allotments::*;
+pub use self::denomination::*;
 pub use self::money_fan::*;
+pub use self::money_time_rate::*;
+pub use self::proration::*;
+pub use self::tally::*;
@@ -4,12 +4,16 @@ use std::ops::{Add, Div, Mul, Neg, Sub};
 use std::str::FromStr;
 
 use iso_4217::CurrencyCode;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use rust_fp_categories::Empty;
 use rust_fp_categories::Monoid;
 use rust_fp_categories::Semigroup;
 use rust_decimal::prelude::{FromPrimitive, Zero};
 
+use crate::money::MoneyTimeRate;
+use crate::time::Duration;
+use crate::util::{BasisPoints, Percentage};
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Money {
   pub amount: Decimal,
@@ -19,6 +23,11 @@ pub struct Money {
 #[derive(Debug, PartialEq)]
 pub enum MoneyError {
   NotSameCurrencyError,
+  NotConvertibleTimeUnitError,
+  EmptyError,
+  DivisionByZeroError,
+  /// 区間の下限が上限より大きい。
+  InvalidIntervalError,
 }
 
 impl Eq for Money {}
@@ -89,6 +98,22 @@ impl Mul<Decimal> for Money {
   }
 }
 
+impl Mul<Percentage> for Money {
+  type Output = Money;
+
+  fn mul(self, rhs: Percentage) -> Self::Output {
+    Money::times(self, rhs.to_factor())
+  }
+}
+
+impl Mul<BasisPoints> for Money {
+  type Output = Money;
+
+  fn mul(self, rhs: BasisPoints) -> Self::Output {
+    Money::times(self, rhs.to_factor())
+  }
+}
+
 impl Div<Decimal> for Money {
   type Output = Money;
 
@@ -214,6 +239,36 @@ impl Money {
       currency: self.currency,
     }
   }
+
+  /// 金額を通貨の小数桁に丸める。
+  pub fn rounded(self, rounding_strategy: RoundingStrategy) -> Self {
+    let scale = self.currency.digit().unwrap();
+    Self {
+      amount: self.amount.round_dp_with_strategy(scale, rounding_strategy),
+      currency: self.currency,
+    }
+  }
+
+  pub fn times_percentage(
+    self,
+    percentage: Percentage,
+    rounding_strategy: RoundingStrategy,
+  ) -> Self {
+    (self * percentage).rounded(rounding_strategy)
+  }
+
+  pub fn times_basis_points(
+    self,
+    basis_points: BasisPoints,
+    rounding_strategy: RoundingStrategy,
+  ) -> Self {
+    (self * basis_points).rounded(rounding_strategy)
+  }
+
+  /// 期間 `duration` あたりの金額を返す。`duration` が長さ0の場合はエラーを返す。
+  pub fn per(self, duration: Duration) -> Result<MoneyTimeRate, MoneyError> {
+    MoneyTimeRate::new(self, duration)
+  }
 }
 
 #[cfg(test)]
@@ -1,15 +1,22 @@
 use core::ops::Add;
-use crate::money::{Allotments, Allotment};
+use std::collections::hash_map::{IntoValues, Values};
+use std::hash::Hash;
+use std::iter::{FromIterator, Sum};
+use std::ops::{Neg, Sub};
+
+use rust_decimal::Decimal;
 use rust_fp_categories::Empty;
-use std::{hash::Hash};
-use rust_fp_categories::Semigroup;
-use std::collections::hash_set::{Iter, IntoIter};
-use std::collections::HashSet;
 
-#[derive(Debug, Clone)]
-pub struct MoneyFan<T>(pub Allotments<T>);
+use crate::money::{Allotment, Allotments, Money, MoneyError};
+use crate::util::Ratio;
+
+/// 複数のエンティティへの金額の割り当て。
+///
+/// 金額が0になった割り当ては加減算・`divide`・`scale`・`merge` の結果から取り除かれる。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneyFan<T: Eq + Hash>(pub Allotments<T>);
 
-impl<T> Empty for MoneyFan<T> {
+impl<T: Eq + Hash> Empty for MoneyFan<T> {
   fn empty() -> Self {
     Self(Allotments::empty())
   }
@@ -19,43 +26,51 @@ impl<T> Empty for MoneyFan<T> {
   }
 }
 
-impl<T> std::iter::IntoIterator for MoneyFan<T> {
+impl<T: Eq + Hash> std::iter::IntoIterator for MoneyFan<T> {
   type Item = Allotment<T>;
-  type IntoIter = IntoIter<Allotment<T>>;
+  type IntoIter = IntoValues<T, Allotment<T>>;
 
   fn into_iter(self) -> Self::IntoIter {
     self.0.into_iter()
   }
 }
 
+/// 同じエンティティに通貨の異なる割り当てがある場合はエラーを返す。
+impl<T: Clone + Eq + Hash> FromIterator<Allotment<T>> for Result<MoneyFan<T>, MoneyError> {
+  fn from_iter<I: IntoIterator<Item = Allotment<T>>>(iter: I) -> Self {
+    Allotments::new(iter).map(MoneyFan::new)
+  }
+}
+
+/// 同じエンティティに通貨の異なる割り当てがある場合はパニックする。[MoneyFan::try_add] はエラーを返す。
 impl<T: Clone + Eq + Hash> Add for MoneyFan<T> {
   type Output = Self;
 
   fn add(self, added: MoneyFan<T>) -> MoneyFan<T> {
-    let all_entities = self
-      .clone()
-      .0
-      .combine(added.0.clone())
-      .into_iter()
-      .map(|e| e.entity)
-      .collect::<HashSet<_>>();
-    let summed_allotments = all_entities
-      .into_iter()
-      .map(|entity| {
-        let allotment = self.clone().allotment(entity.clone());
-        match allotment {
-          Option::None => added.clone().allotment(entity).unwrap(),
-          Option::Some(this_allotment) => match added.clone().allotment(entity.clone()) {
-            Option::None => this_allotment,
-            Option::Some(added_allotment) => {
-              Allotment::new(entity, this_allotment.amount + added_allotment.amount)
-            }
-          },
-        }
-      })
-      .filter(|e| !e.amount.is_zero())
-      .collect::<HashSet<_>>();
-    MoneyFan::new(Allotments::new(summed_allotments))
+    MoneyFan::try_add(self, added).unwrap_or_else(|err| panic!("{:?}", err))
+  }
+}
+
+/// 同じエンティティに通貨の異なる割り当てがある場合はパニックする。[MoneyFan::subtract] はエラーを返す。
+impl<T: Clone + Eq + Hash> Sub for MoneyFan<T> {
+  type Output = Self;
+
+  fn sub(self, subtracted: MoneyFan<T>) -> MoneyFan<T> {
+    MoneyFan::subtract(self, subtracted).unwrap_or_else(|err| panic!("{:?}", err))
+  }
+}
+
+impl<T: Clone + Eq + Hash> Neg for MoneyFan<T> {
+  type Output = Self;
+
+  fn neg(self) -> MoneyFan<T> {
+    MoneyFan::negated(self)
+  }
+}
+
+impl<T: Clone + Eq + Hash> Sum for MoneyFan<T> {
+  fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+    iter.fold(MoneyFan::empty(), |acc, e| acc + e)
   }
 }
 
@@ -64,41 +79,261 @@ impl<T: Clone + Eq + Hash> MoneyFan<T> {
     Self(values)
   }
 
-  pub fn iter(&self) -> Iter<Allotment<T>> {
+  pub fn iter(&self) -> Values<'_, T, Allotment<T>> {
     self.0.iter()
   }
 
-  pub fn allotment(self, an_entity: T) -> Option<Allotment<T>> {
-    self.0.find(|e| e.entity == an_entity)
+  pub fn allotment(&self, an_entity: &T) -> Option<&Allotment<T>> {
+    self.0.get(an_entity)
+  }
+
+  pub fn amount_of(&self, an_entity: &T) -> Option<&Money> {
+    self.allotment(an_entity).map(|e| &e.amount)
   }
 
-  pub fn subtract(self, subtracted: MoneyFan<T>) -> MoneyFan<T> {
-    self.add(subtracted.negated())
+  /// 全エンティティへの割り当て金額の合計を返す。
+  ///
+  /// 割り当てが1つもない場合、または通貨が混在している場合はエラーを返す。
+  pub fn total(&self) -> Result<Money, MoneyError> {
+    let mut amounts = self.iter().map(|e| e.amount.clone());
+    let first = amounts.next().ok_or(MoneyError::EmptyError)?;
+    amounts.try_fold(first, |acc, e| acc.add(e))
+  }
+
+  /// 2つの割り当てを合算する。同じエンティティに通貨の異なる割り当てがある場合はエラーを返す。
+  pub fn try_add(self, added: MoneyFan<T>) -> Result<MoneyFan<T>, MoneyError> {
+    self
+      .0
+      .try_combine(added.0)
+      .map(MoneyFan::without_zero_allotments)
+  }
+
+  /// 割り当てを差し引く。同じエンティティに通貨の異なる割り当てがある場合はエラーを返す。
+  pub fn subtract(self, subtracted: MoneyFan<T>) -> Result<MoneyFan<T>, MoneyError> {
+    MoneyFan::try_add(self, subtracted.negated())
   }
 
   pub fn negated(self) -> MoneyFan<T> {
     MoneyFan::new(self.0.negated())
   }
+
+  /// 各割り当て金額を `ratio` で割る。金額は丸めない。
+  ///
+  /// `ratio` がゼロの場合はエラーを返す。
+  pub fn divide(self, ratio: &Ratio) -> Result<MoneyFan<T>, MoneyError> {
+    if ratio.to_numerator().is_zero() {
+      return Err(MoneyError::DivisionByZeroError);
+    }
+    Ok(self.map_amounts(|e| {
+      e.times(ratio.to_denominator())
+        .divided_by(ratio.to_numerator())
+    }))
+  }
+
+  /// 各割り当て金額に `factor` を掛ける。金額は丸めない。
+  pub fn scale(self, factor: Decimal) -> MoneyFan<T> {
+    self.map_amounts(|e| e.times(factor))
+  }
+
+  /// 2つの割り当てを統合する。
+  ///
+  /// 両方に同じエンティティが含まれる場合、その金額は `f` で決める。
+  pub fn merge<F>(self, other: MoneyFan<T>, f: F) -> MoneyFan<T>
+  where
+    F: Fn(Money, Money) -> Money,
+  {
+    let mut merged = self.0;
+    for allotment in other {
+      let amount = match merged.remove(&allotment.entity) {
+        Some(current) => f(current.amount, allotment.amount),
+        None => allotment.amount,
+      };
+      merged.0.insert(
+        allotment.entity.clone(),
+        Allotment::new(allotment.entity, amount),
+      );
+    }
+    MoneyFan::without_zero_allotments(merged)
+  }
+
+  pub fn retain<F>(&mut self, f: F)
+  where
+    F: Fn(&Allotment<T>) -> bool,
+  {
+    self.0.retain(f)
+  }
+
+  /// 各割り当て金額を `f` で変換する。エンティティは変わらないため合算は起きない。
+  fn map_amounts<F>(self, f: F) -> MoneyFan<T>
+  where
+    F: Fn(Money) -> Money,
+  {
+    let values = (self.0)
+      .0
+      .into_iter()
+      .map(|(k, v)| (k, Allotment::new(v.entity, f(v.amount))))
+      .collect();
+    MoneyFan::without_zero_allotments(Allotments(values))
+  }
+
+  fn without_zero_allotments(mut allotments: Allotments<T>) -> MoneyFan<T> {
+    allotments.retain(|e| !e.amount.is_zero());
+    MoneyFan::new(allotments)
+  }
 }
 
 #[cfg(test)]
 mod tests {
-  use crate::money::{Money, Allotment, MoneyFan, Allotments};
   use std::collections::HashSet;
+  use std::str::FromStr;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::Decimal;
+  use rust_fp_categories::Empty;
+
+  use crate::money::{
+    Allotment, Allotments, Money, MoneyError, MoneyFan, Proration, RemainderAllocation,
+  };
+  use crate::util::Ratio;
+
+  fn fan(allotments: Vec<(&'static str, i32)>) -> MoneyFan<&'static str> {
+    allotments
+      .into_iter()
+      .map(|(entity, amount)| Allotment::new(entity, Money::dollars_i32(amount)))
+      .collect::<Result<_, _>>()
+      .unwrap()
+  }
+
+  fn shared_evenly(bill: Money, roommates: &[&'static str]) -> MoneyFan<&'static str> {
+    let shares =
+      Proration::divided_evenly_into_parts(bill, roommates.len(), RemainderAllocation::First)
+        .unwrap();
+    roommates
+      .iter()
+      .zip(shares)
+      .map(|(entity, share)| Allotment::new(*entity, share))
+      .collect::<Result<_, _>>()
+      .unwrap()
+  }
 
   #[test]
   fn test01_roommate_example() {
     let a = Allotment::new("Joe", Money::dollars_f32(65.00));
     let mut h = HashSet::new();
     h.insert(a);
-    let _electric_bill = MoneyFan::new(Allotments::new(h));
+    let electric_bill = MoneyFan::new(Allotments::new(h).unwrap());
 
     let mut h = HashSet::new();
     h.insert(Allotment::new("Mary", Money::dollars_i32(650)));
     h.insert(Allotment::new("Jill", Money::dollars_i32(650)));
     h.insert(Allotment::new("Joe", Money::dollars_i32(650)));
-    let _rent = MoneyFan::new(Allotments::new(h));
+    let rent = MoneyFan::new(Allotments::new(h).unwrap());
 
-    // TODO
+    let groceries = fan(vec![("Jill", 127)]);
+
+    // 家賃は Mary、電気代は Joe、食費は Jill が全額を立て替えた
+    let paid = fan(vec![("Mary", 1950)]) + electric_bill.clone() + groceries.clone();
+    assert_eq!(paid.total(), Ok(Money::dollars_i32(2142)));
+
+    let roommates = ["Joe", "Mary", "Jill"];
+    let owed = rent
+      + shared_evenly(electric_bill.total().unwrap(), &roommates)
+      + shared_evenly(groceries.total().unwrap(), &roommates);
+    assert_eq!(owed.total(), paid.total());
+
+    let settlement = owed - paid;
+    assert_eq!(
+      settlement.amount_of(&"Joe"),
+      Some(&Money::from(("649.01", CurrencyCode::USD)))
+    );
+    assert_eq!(
+      settlement.amount_of(&"Mary"),
+      Some(&Money::from(("-1236.00", CurrencyCode::USD)))
+    );
+    assert_eq!(
+      settlement.amount_of(&"Jill"),
+      Some(&Money::from(("586.99", CurrencyCode::USD)))
+    );
+    assert_eq!(settlement.total(), Ok(Money::dollars_i32(0)));
+  }
+
+  #[test]
+  fn test02_add_removes_zero_allotments() {
+    let a = fan(vec![("Joe", 10), ("Mary", 20)]);
+    let b = fan(vec![("Joe", -10), ("Jill", 5)]);
+    let sum = a + b;
+    assert_eq!(sum.allotment(&"Joe"), None);
+    assert_eq!(sum, fan(vec![("Mary", 20), ("Jill", 5)]));
+  }
+
+  #[test]
+  fn test03_total() {
+    assert_eq!(
+      MoneyFan::<&str>::empty().total(),
+      Err(MoneyError::EmptyError)
+    );
+    let mixed: MoneyFan<&str> = vec![
+      Allotment::new("Joe", Money::dollars_i32(1)),
+      Allotment::new("Mary", Money::from((1u32, CurrencyCode::JPY))),
+    ]
+    .into_iter()
+    .collect::<Result<_, _>>()
+    .unwrap();
+    assert_eq!(mixed.total(), Err(MoneyError::NotSameCurrencyError));
+
+    let yen = MoneyFan::new(
+      Allotments::new(vec![Allotment::new(
+        "Joe",
+        Money::from((1u32, CurrencyCode::JPY)),
+      )])
+      .unwrap(),
+    );
+    assert_eq!(
+      MoneyFan::try_add(fan(vec![("Joe", 1)]), yen),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+  }
+
+  #[test]
+  fn test04_divide_and_scale() {
+    let f = fan(vec![("Joe", 30), ("Mary", 60)]);
+    assert_eq!(
+      f.clone().divide(&Ratio::new_i64(3, 1)),
+      Ok(fan(vec![("Joe", 10), ("Mary", 20)]))
+    );
+    assert_eq!(
+      f.clone().divide(&Ratio::new_i64(0, 1)),
+      Err(MoneyError::DivisionByZeroError)
+    );
+    assert_eq!(
+      f.clone().scale(Decimal::from_str("0.5").unwrap()),
+      fan(vec![("Joe", 15), ("Mary", 30)])
+    );
+    assert!(f.scale(Decimal::ZERO).is_empty());
+  }
+
+  #[test]
+  fn test05_merge_and_retain() {
+    let a = fan(vec![("Joe", 10), ("Mary", 20)]);
+    let b = fan(vec![("Joe", 30), ("Jill", 5)]);
+    let mut merged = a.merge(b, |x, y| if x > y { x } else { y });
+    assert_eq!(merged, fan(vec![("Joe", 30), ("Mary", 20), ("Jill", 5)]));
+
+    merged.retain(|e| e.amount > Money::dollars_i32(10));
+    assert_eq!(merged, fan(vec![("Joe", 30), ("Mary", 20)]));
+
+    let cancelled = merged.merge(fan(vec![("Joe", -30)]), |x, y| x + y);
+    assert_eq!(cancelled, fan(vec![("Mary", 20)]));
+  }
+
+  #[test]
+  fn test06_sum() {
+    let fans = vec![
+      fan(vec![("Joe", 10)]),
+      fan(vec![("Joe", 5), ("Mary", 1)]),
+      fan(vec![("Mary", 2)]),
+    ];
+    let sum: MoneyFan<&str> = fans.into_iter().sum();
+    assert_eq!(sum, fan(vec![("Joe", 15), ("Mary", 3)]));
   }
 }
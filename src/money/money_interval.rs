@@ -0,0 +1,176 @@
+use iso_4217::CurrencyCode;
+
+use crate::intervals::{Interval, IntervalError, LimitValue};
+use crate::money::{Money, MoneyError};
+
+impl From<IntervalError> for MoneyError {
+  fn from(error: IntervalError) -> Self {
+    match error {
+      IntervalError::NotOrderedError => MoneyError::InvalidIntervalError,
+      IntervalError::IncomparableError => MoneyError::NotSameCurrencyError,
+    }
+  }
+}
+
+impl Money {
+  /// `lower` 以上 `upper` 以下の金額の区間を返す。
+  ///
+  /// 通貨が異なる場合、または `lower` が `upper` より大きい場合はエラーを返す。
+  pub fn between(lower: Money, upper: Money) -> Result<Interval<Money>, MoneyError> {
+    Interval::money_over(lower, true, upper, true)
+  }
+
+  /// `lower` 以上の金額の区間を返す。
+  pub fn at_least(lower: Money) -> Interval<Money> {
+    Interval::and_more(LimitValue::Limit(lower))
+  }
+
+  /// `upper` 以下の金額の区間を返す。
+  pub fn at_most(upper: Money) -> Interval<Money> {
+    Interval::up_to(LimitValue::Limit(upper))
+  }
+
+  /// この金額が区間 `interval` に含まれるかを返す。通貨が異なる場合はエラーを返す。
+  pub fn is_within(&self, interval: &Interval<Money>) -> Result<bool, MoneyError> {
+    interval.includes_money(self)
+  }
+}
+
+impl Interval<Money> {
+  /// 金額の区間を生成する。
+  ///
+  /// 両端の通貨が異なる場合、または `lower` が `upper` より大きい場合はエラーを返す。
+  pub fn money_over(
+    lower: Money,
+    lower_included: bool,
+    upper: Money,
+    upper_included: bool,
+  ) -> Result<Self, MoneyError> {
+    if lower.currency != upper.currency {
+      return Err(MoneyError::NotSameCurrencyError);
+    }
+    Ok(Interval::try_over(
+      LimitValue::Limit(lower),
+      lower_included,
+      LimitValue::Limit(upper),
+      upper_included,
+    )?)
+  }
+
+  /// 区間の通貨を返す。上限・下限ともにない場合は `None`。
+  pub fn currency(&self) -> Option<CurrencyCode> {
+    match (self.lower_limit(), self.upper_limit()) {
+      (LimitValue::Limit(money), _) | (_, LimitValue::Limit(money)) => Some(money.currency),
+      _ => None,
+    }
+  }
+
+  /// 金額 `money` が区間に含まれるかを返す。
+  ///
+  /// [Interval::includes] と異なり、通貨が異なる場合は含まれないとせずにエラーを返す。
+  pub fn includes_money(&self, money: &Money) -> Result<bool, MoneyError> {
+    Ok(self.try_includes(&LimitValue::Limit(money.clone()))?)
+  }
+
+  /// 金額の区間 `other` と共通部分を持つかを返す。
+  ///
+  /// [Interval::intersects] と異なり、通貨が異なる場合は共通部分がないとせずにエラーを返す。
+  pub fn intersects_money(&self, other: &Interval<Money>) -> Result<bool, MoneyError> {
+    Ok(self.try_intersects(other)?)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+
+  use crate::intervals::{Interval, IntervalError, LimitValue};
+  use crate::money::{Money, MoneyError};
+
+  fn yen(amount: u32) -> Money {
+    Money::from((amount, CurrencyCode::JPY))
+  }
+
+  #[test]
+  fn test01_between() {
+    let range = Money::between(yen(1_000), yen(100_000)).unwrap();
+    assert_eq!(range.currency(), Some(CurrencyCode::JPY));
+    assert_eq!(range.includes_money(&yen(1_000)), Ok(true));
+    assert_eq!(range.includes_money(&yen(100_000)), Ok(true));
+    assert_eq!(range.includes_money(&yen(999)), Ok(false));
+    assert_eq!(yen(50_000).is_within(&range), Ok(true));
+    assert_eq!(
+      range.includes_money(&Money::dollars_i32(500)),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+
+    assert_eq!(
+      Money::between(yen(1), Money::dollars_i32(1)).map(|_| ()),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+    assert_eq!(
+      Money::between(yen(10), yen(1)).map(|_| ()),
+      Err(MoneyError::InvalidIntervalError)
+    );
+  }
+
+  #[test]
+  fn test03_generic_interval() {
+    let range = Money::between(yen(1), yen(10)).unwrap();
+    let dollars = LimitValue::Limit(Money::dollars_i32(5));
+    assert!(!range.includes(&dollars));
+    assert_eq!(
+      range.try_includes(&dollars),
+      Err(IntervalError::IncomparableError)
+    );
+    assert_eq!(
+      Interval::try_over(
+        LimitValue::Limit(yen(1)),
+        true,
+        LimitValue::Limit(Money::dollars_i32(1)),
+        true,
+      )
+      .map(|_| ()),
+      Err(IntervalError::IncomparableError)
+    );
+
+    let dollars = Money::between(Money::dollars_i32(1), Money::dollars_i32(10)).unwrap();
+    assert!(!range.intersects(&dollars));
+    assert_eq!(
+      range.try_intersects(&dollars),
+      Err(IntervalError::IncomparableError)
+    );
+    assert_eq!(
+      range.intersects_money(&dollars),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+    assert_eq!(
+      range.intersects_money(&Money::between(yen(5), yen(20)).unwrap()),
+      Ok(true)
+    );
+    assert_eq!(range.intersects_money(&Money::at_least(yen(11))), Ok(false));
+    assert_eq!(
+      Money::at_least(Money::dollars_i32(1)).intersects_money(&Money::at_most(yen(1))),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+  }
+
+  #[test]
+  fn test02_half_bounded() {
+    let minimum = Money::at_least(yen(1_000));
+    assert_eq!(minimum.includes_money(&yen(1_000_000)), Ok(true));
+    assert_eq!(
+      minimum.includes_money(&Money::dollars_i32(1)),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+    assert_eq!(Money::at_most(yen(10)).includes_money(&yen(11)), Ok(false));
+
+    let exclusive = Interval::money_over(yen(0), false, yen(10), false).unwrap();
+    assert_eq!(exclusive.includes_money(&yen(0)), Ok(false));
+
+    let unbounded: Interval<Money> =
+      Interval::over(LimitValue::Limitless, false, LimitValue::Limitless, false);
+    assert_eq!(unbounded.currency(), None);
+    assert_eq!(unbounded.includes_money(&Money::dollars_i32(1)), Ok(true));
+  }
+}
@@ -0,0 +1,226 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::money::{Money, MoneyError};
+use crate::time::{Duration, TimeUnit};
+
+/// 単位時間あたりの金額（例: 1時間あたり $0.12、1年あたり ¥5,000,000）。
+#[derive(Debug, Clone)]
+pub struct MoneyTimeRate {
+  money: Money,
+  duration: Duration,
+}
+
+impl PartialEq for MoneyTimeRate {
+  fn eq(&self, other: &Self) -> bool {
+    self.partial_cmp(other) == Some(Ordering::Equal)
+  }
+}
+
+impl PartialOrd for MoneyTimeRate {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.compare(other).ok()
+  }
+}
+
+impl TryFrom<(Money, Duration)> for MoneyTimeRate {
+  type Error = MoneyError;
+
+  fn try_from((money, duration): (Money, Duration)) -> Result<Self, Self::Error> {
+    Self::new(money, duration)
+  }
+}
+
+impl MoneyTimeRate {
+  /// コンストラクタ。
+  ///
+  /// `duration` が長さ0の場合はエラーを返す。
+  pub fn new(money: Money, duration: Duration) -> Result<Self, MoneyError> {
+    if duration.to_quantity() == 0 {
+      return Err(MoneyError::DivisionByZeroError);
+    }
+    Ok(Self { money, duration })
+  }
+
+  pub fn as_money(&self) -> &Money {
+    &self.money
+  }
+
+  pub fn as_duration(&self) -> &Duration {
+    &self.duration
+  }
+
+  /// 期間 `duration` に対する金額を、通貨の小数桁に丸めて返す。
+  pub fn over(
+    &self,
+    duration: &Duration,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, MoneyError> {
+    let scale = self.money.currency.digit().unwrap();
+    self.over_with_scale(duration, scale, rounding_strategy)
+  }
+
+  /// 期間 `duration` に対する金額を、小数桁 `scale` に丸めて返す。
+  ///
+  /// この比率の期間と `duration` の単位が相互に変換できない場合（月単位とミリ秒単位）はエラーを返す。
+  pub fn over_with_scale(
+    &self,
+    duration: &Duration,
+    scale: u32,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, MoneyError> {
+    self.check_convertible(duration.as_unit())?;
+    let amount =
+      self.money.amount * Self::in_base_units(duration) / Self::in_base_units(&self.duration);
+    Ok(Money {
+      amount: amount.round_dp_with_strategy(scale, rounding_strategy),
+      currency: self.money.currency,
+    })
+  }
+
+  /// この比率を `unit` 1単位あたりの比率に変換する。
+  ///
+  /// 変換後の金額は丸めない。
+  pub fn to_unit(&self, unit: TimeUnit) -> Result<Self, MoneyError> {
+    self.check_convertible(&unit)?;
+    let per_unit = Duration::new(1, unit);
+    let money = self
+      .money
+      .clone()
+      .times(Self::in_base_units(&per_unit))
+      .divided_by(Self::in_base_units(&self.duration));
+    Self::new(money, per_unit)
+  }
+
+  /// 2つの比率を比較する。
+  ///
+  /// 通貨が異なる場合、または時間の単位が相互に変換できない場合はエラーを返す。
+  pub fn compare(&self, other: &Self) -> Result<Ordering, MoneyError> {
+    if self.money.currency != other.money.currency {
+      return Err(MoneyError::NotSameCurrencyError);
+    }
+    self.check_convertible(other.duration.as_unit())?;
+    let lhs = self.money.amount * Self::in_base_units(&other.duration);
+    let rhs = other.money.amount * Self::in_base_units(&self.duration);
+    Ok(lhs.cmp(&rhs))
+  }
+
+  /// 期間を基本単位（ミリ秒または月）の数に換算する。`i64` で桁あふれしないよう `Decimal` で計算する。
+  fn in_base_units(duration: &Duration) -> Decimal {
+    Decimal::from(duration.to_quantity()) * Decimal::from(duration.as_unit().factor())
+  }
+
+  fn check_convertible(&self, unit: &TimeUnit) -> Result<(), MoneyError> {
+    if self.duration.as_unit().is_convertible_to(unit) {
+      Ok(())
+    } else {
+      Err(MoneyError::NotConvertibleTimeUnitError)
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::cmp::Ordering;
+  use std::convert::TryFrom;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::RoundingStrategy;
+
+  use crate::money::{Money, MoneyError, MoneyTimeRate};
+  use crate::time::{Duration, TimeUnit};
+
+  #[test]
+  fn test01_over() {
+    let rate =
+      MoneyTimeRate::new(Money::from(("0.12", CurrencyCode::USD)), Duration::hours(1)).unwrap();
+    assert_eq!(
+      rate.over(
+        &Duration::minutes(90),
+        RoundingStrategy::MidpointAwayFromZero
+      ),
+      Ok(Money::from(("0.18", CurrencyCode::USD)))
+    );
+    assert_eq!(
+      rate.over(&Duration::days(30), RoundingStrategy::MidpointAwayFromZero),
+      Ok(Money::from(("86.40", CurrencyCode::USD)))
+    );
+  }
+
+  #[test]
+  fn test02_over_month_based() {
+    let salary = Money::per(
+      Money::from((5_000_000u32, CurrencyCode::JPY)),
+      Duration::years(1),
+    )
+    .unwrap();
+    assert_eq!(
+      salary.over(&Duration::months(1), RoundingStrategy::MidpointAwayFromZero),
+      Ok(Money::from((416_667u32, CurrencyCode::JPY)))
+    );
+    assert_eq!(
+      salary.over(&Duration::months(1), RoundingStrategy::ToZero),
+      Ok(Money::from((416_666u32, CurrencyCode::JPY)))
+    );
+    assert_eq!(
+      salary.over(&Duration::days(1), RoundingStrategy::ToZero),
+      Err(MoneyError::NotConvertibleTimeUnitError)
+    );
+  }
+
+  #[test]
+  fn test03_to_unit() {
+    let rate =
+      MoneyTimeRate::new(Money::from(("0.12", CurrencyCode::USD)), Duration::hours(1)).unwrap();
+    let per_minute = rate.to_unit(TimeUnit::MINUTE).unwrap();
+    assert_eq!(per_minute.as_duration(), &Duration::minutes(1));
+    assert_eq!(
+      per_minute.as_money().amount.normalize().to_string(),
+      "0.002"
+    );
+    assert_eq!(per_minute, rate);
+    assert_eq!(
+      rate.to_unit(TimeUnit::MONTH).unwrap_err(),
+      MoneyError::NotConvertibleTimeUnitError
+    );
+  }
+
+  #[test]
+  fn test04_compare() {
+    let hourly =
+      MoneyTimeRate::new(Money::from(("6", CurrencyCode::USD)), Duration::hours(1)).unwrap();
+    let per_minute = MoneyTimeRate::new(
+      Money::from(("0.11", CurrencyCode::USD)),
+      Duration::minutes(1),
+    )
+    .unwrap();
+    let yearly =
+      MoneyTimeRate::new(Money::from(("6", CurrencyCode::USD)), Duration::years(1)).unwrap();
+    let yen =
+      MoneyTimeRate::new(Money::from((6u32, CurrencyCode::JPY)), Duration::hours(1)).unwrap();
+
+    assert_eq!(hourly.compare(&per_minute), Ok(Ordering::Less));
+    assert!(per_minute > hourly);
+    assert_eq!(
+      hourly.compare(&yearly),
+      Err(MoneyError::NotConvertibleTimeUnitError)
+    );
+    assert_eq!(hourly.partial_cmp(&yearly), None);
+    assert_eq!(hourly.compare(&yen), Err(MoneyError::NotSameCurrencyError));
+  }
+
+  #[test]
+  fn test05_zero_duration() {
+    let money = Money::from(("6", CurrencyCode::USD));
+    assert_eq!(
+      MoneyTimeRate::new(money.clone(), Duration::hours(0)).unwrap_err(),
+      MoneyError::DivisionByZeroError
+    );
+    assert_eq!(
+      MoneyTimeRate::try_from((money, Duration::hours(1))).unwrap(),
+      MoneyTimeRate::new(Money::from(("6", CurrencyCode::USD)), Duration::hours(1)).unwrap()
+    );
+  }
+}
@@ -0,0 +1,314 @@
+use rust_decimal::prelude::{ToPrimitive, Zero};
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::intervals::{Interval, LimitValue};
+use crate::money::Money;
+use crate::time::TimePoint;
+use crate::util::Ratio;
+
+/// 按分で割り切れなかった端数（最小通貨単位）の割り当て規則。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum RemainderAllocation {
+  /// 先頭の要素から順に1単位ずつ割り当てる。
+  First,
+  /// 末尾の要素から順に1単位ずつ割り当てる。
+  Last,
+  /// 切り捨てた端数の大きい要素から順に割り当てる（最大剰余法）。
+  LargestRemainder,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ProrationError {
+  NoPartsError,
+  NegativeWeightError,
+  ZeroTotalWeightError,
+  UnboundedIntervalError,
+  OutOfPeriodError,
+}
+
+/// 金額を按分するためのユーティリティ。
+///
+/// 按分結果の合計は、常に按分前の金額（通貨の小数桁に丸めたもの）と一致する。
+pub struct Proration;
+
+impl Proration {
+  /// 金額を `n` 等分する。
+  pub fn divided_evenly_into_parts(
+    total: Money,
+    n: usize,
+    allocation: RemainderAllocation,
+  ) -> Result<Vec<Money>, ProrationError> {
+    Self::prorate(total, &vec![Decimal::ONE; n], allocation)
+  }
+
+  /// 金額を `ratios` の比で按分する。
+  pub fn prorated_over(
+    total: Money,
+    ratios: &[Ratio],
+    allocation: RemainderAllocation,
+  ) -> Result<Vec<Money>, ProrationError> {
+    let weights = ratios
+      .iter()
+      .map(|ratio| ratio.decimal_value(0, None))
+      .collect::<Vec<_>>();
+    Self::prorate(total, &weights, allocation)
+  }
+
+  /// 金額を各区間の長さの比で按分する。
+  ///
+  /// 区間はすべて上限・下限を持っていなければならない。
+  pub fn prorated_over_intervals(
+    total: Money,
+    intervals: &[Interval<TimePoint>],
+    allocation: RemainderAllocation,
+  ) -> Result<Vec<Money>, ProrationError> {
+    let weights = intervals
+      .iter()
+      .map(Self::length_in_milliseconds)
+      .collect::<Result<Vec<_>, _>>()?;
+    Self::prorate(total, &weights, allocation)
+  }
+
+  /// 期間 `period` に対する金額のうち、利用期間 `used` に相当する金額を返す。
+  ///
+  /// 例えば月額料金を、その月のうち利用した日数分だけ日割りする場合に使う。
+  pub fn prorated_for_period(
+    total: Money,
+    period: &Interval<TimePoint>,
+    used: &Interval<TimePoint>,
+    allocation: RemainderAllocation,
+  ) -> Result<Money, ProrationError> {
+    let period_length = Self::length_in_milliseconds(period)?;
+    let used_length = Self::length_in_milliseconds(used)?;
+    if used.lower_limit() < period.lower_limit() || used.upper_limit() > period.upper_limit() {
+      return Err(ProrationError::OutOfPeriodError);
+    }
+    let mut parts = Self::prorate(
+      total,
+      &[used_length, period_length - used_length],
+      allocation,
+    )?;
+    Ok(parts.remove(0))
+  }
+
+  fn length_in_milliseconds(interval: &Interval<TimePoint>) -> Result<Decimal, ProrationError> {
+    match (interval.lower_limit(), interval.upper_limit()) {
+      (LimitValue::Limit(lower), LimitValue::Limit(upper)) => Ok(Decimal::from(
+        upper.milliseconds_from_epoc() - lower.milliseconds_from_epoc(),
+      )),
+      _ => Err(ProrationError::UnboundedIntervalError),
+    }
+  }
+
+  fn prorate(
+    total: Money,
+    weights: &[Decimal],
+    allocation: RemainderAllocation,
+  ) -> Result<Vec<Money>, ProrationError> {
+    if weights.is_empty() {
+      return Err(ProrationError::NoPartsError);
+    }
+    if weights.iter().any(|w| *w < Decimal::zero()) {
+      return Err(ProrationError::NegativeWeightError);
+    }
+    let weight_sum = weights.iter().sum::<Decimal>();
+    if weight_sum.is_zero() {
+      return Err(ProrationError::ZeroTotalWeightError);
+    }
+
+    let total = Money::new(total.amount, total.currency);
+    let scale = total.currency.digit().unwrap();
+    let (mut shares, remainders): (Vec<Decimal>, Vec<Decimal>) = weights
+      .iter()
+      .map(|weight| {
+        let exact = total.amount * (*weight / weight_sum);
+        let share = exact.round_dp_with_strategy(scale, RoundingStrategy::ToZero);
+        (share, (exact - share).abs())
+      })
+      .unzip();
+
+    let unit = Decimal::new(1, scale);
+    let residue = total.amount - shares.iter().sum::<Decimal>();
+    let steps = (residue / unit).to_i64().unwrap();
+    let step = if steps < 0 { -unit } else { unit };
+    let order = Self::allocation_order(&remainders, allocation);
+    for i in 0..steps.unsigned_abs() as usize {
+      shares[order[i % order.len()]] += step;
+    }
+
+    Ok(
+      shares
+        .into_iter()
+        .map(|share| Money::new(share, total.currency))
+        .collect(),
+    )
+  }
+
+  fn allocation_order(remainders: &[Decimal], allocation: RemainderAllocation) -> Vec<usize> {
+    let mut order = (0..remainders.len()).collect::<Vec<_>>();
+    match allocation {
+      RemainderAllocation::First => {}
+      RemainderAllocation::Last => order.reverse(),
+      RemainderAllocation::LargestRemainder => {
+        order.sort_by(|a, b| remainders[*b].cmp(&remainders[*a]))
+      }
+    }
+    order
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+  use rust_decimal::Decimal;
+  use std::str::FromStr;
+
+  use crate::intervals::{Interval, LimitValue};
+  use crate::money::{Money, Proration, ProrationError, RemainderAllocation};
+  use crate::time::TimePoint;
+  use crate::util::Ratio;
+
+  fn sum(parts: &[Money]) -> Money {
+    parts
+      .iter()
+      .cloned()
+      .fold(Money::zero(parts[0].currency), |acc, e| acc + e)
+  }
+
+  fn day(month: u32, day: u32) -> LimitValue<TimePoint> {
+    LimitValue::Limit(TimePoint::at_ymd_hms_milli_utc(
+      2020, month, day, 0, 0, 0, 0,
+    ))
+  }
+
+  #[test]
+  fn test01_divided_evenly_into_parts() {
+    let total = Money::from(("100", CurrencyCode::USD));
+
+    let parts =
+      Proration::divided_evenly_into_parts(total.clone(), 3, RemainderAllocation::First).unwrap();
+    assert_eq!(
+      parts,
+      vec![
+        Money::from(("33.34", CurrencyCode::USD)),
+        Money::from(("33.33", CurrencyCode::USD)),
+        Money::from(("33.33", CurrencyCode::USD)),
+      ]
+    );
+    assert_eq!(sum(&parts), total);
+
+    let parts =
+      Proration::divided_evenly_into_parts(total.clone(), 3, RemainderAllocation::Last).unwrap();
+    assert_eq!(parts[2], Money::from(("33.34", CurrencyCode::USD)));
+    assert_eq!(sum(&parts), total);
+  }
+
+  #[test]
+  fn test02_negative_total() {
+    let total = Money::from(("-10", CurrencyCode::USD));
+    let parts =
+      Proration::divided_evenly_into_parts(total.clone(), 3, RemainderAllocation::First).unwrap();
+    assert_eq!(parts[0], Money::from(("-3.34", CurrencyCode::USD)));
+    assert_eq!(sum(&parts), total);
+  }
+
+  #[test]
+  fn test03_prorated_over_ratios() {
+    let total = Money::from((10000u32, CurrencyCode::JPY));
+    let ratios = vec![
+      Ratio::new_i64(1, 3),
+      Ratio::new_i64(1, 3),
+      Ratio::new_i64(1, 6),
+      Ratio::new_i64(1, 6),
+    ];
+    let parts = Proration::prorated_over(
+      total.clone(),
+      &ratios,
+      RemainderAllocation::LargestRemainder,
+    )
+    .unwrap();
+    assert_eq!(
+      parts,
+      vec![
+        Money::from((3333u32, CurrencyCode::JPY)),
+        Money::from((3333u32, CurrencyCode::JPY)),
+        Money::from((1667u32, CurrencyCode::JPY)),
+        Money::from((1667u32, CurrencyCode::JPY)),
+      ]
+    );
+    assert_eq!(sum(&parts), total);
+  }
+
+  #[test]
+  fn test04_largest_remainder() {
+    let total = Money::from(("1", CurrencyCode::USD));
+    let weights = vec![
+      Ratio::new(Decimal::from_str("0.114").unwrap(), Decimal::ONE),
+      Ratio::new(Decimal::from_str("0.443").unwrap(), Decimal::ONE),
+      Ratio::new(Decimal::from_str("0.443").unwrap(), Decimal::ONE),
+    ];
+    let parts = Proration::prorated_over(
+      total.clone(),
+      &weights,
+      RemainderAllocation::LargestRemainder,
+    )
+    .unwrap();
+    assert_eq!(parts[0], Money::from(("0.12", CurrencyCode::USD)));
+    assert_eq!(sum(&parts), total);
+  }
+
+  #[test]
+  fn test05_prorated_for_period() {
+    let monthly_fee = Money::from((3000u32, CurrencyCode::JPY));
+    let april = Interval::over(day(4, 1), true, day(5, 1), false);
+    let used = Interval::over(day(4, 21), true, day(5, 1), false);
+    let prorated =
+      Proration::prorated_for_period(monthly_fee, &april, &used, RemainderAllocation::First)
+        .unwrap();
+    assert_eq!(prorated, Money::from((1000u32, CurrencyCode::JPY)));
+
+    let outside = Interval::over(day(4, 21), true, LimitValue::Limitless, false);
+    assert_eq!(
+      Proration::prorated_for_period(
+        Money::from((3000u32, CurrencyCode::JPY)),
+        &april,
+        &outside,
+        RemainderAllocation::First
+      ),
+      Err(ProrationError::UnboundedIntervalError)
+    );
+  }
+
+  #[test]
+  fn test06_prorated_over_intervals() {
+    let total = Money::from(("100", CurrencyCode::USD));
+    let intervals = vec![
+      Interval::over(day(4, 1), true, day(4, 11), false),
+      Interval::over(day(4, 11), true, day(4, 21), false),
+      Interval::over(day(4, 21), true, day(4, 30), false),
+    ];
+    let parts =
+      Proration::prorated_over_intervals(total.clone(), &intervals, RemainderAllocation::First)
+        .unwrap();
+    assert_eq!(parts[0], Money::from(("34.49", CurrencyCode::USD)));
+    assert_eq!(parts[2], Money::from(("31.03", CurrencyCode::USD)));
+    assert_eq!(sum(&parts), total);
+  }
+
+  #[test]
+  fn test07_errors() {
+    let total = Money::from(("100", CurrencyCode::USD));
+    assert_eq!(
+      Proration::divided_evenly_into_parts(total.clone(), 0, RemainderAllocation::First),
+      Err(ProrationError::NoPartsError)
+    );
+    assert_eq!(
+      Proration::prorated_over(
+        total,
+        &[Ratio::new_i64(-1, 2), Ratio::new_i64(1, 2)],
+        RemainderAllocation::First
+      ),
+      Err(ProrationError::NegativeWeightError)
+    );
+  }
+}
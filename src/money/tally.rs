@@ -0,0 +1,174 @@
+use std::iter::FromIterator;
+
+use iso_4217::CurrencyCode;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::money::{Money, MoneyError};
+
+/// 金額の集まりに対する集計。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tally(Vec<Money>);
+
+impl FromIterator<Money> for Tally {
+  fn from_iter<I: IntoIterator<Item = Money>>(iter: I) -> Self {
+    Self::new(iter.into_iter().collect())
+  }
+}
+
+impl Tally {
+  pub fn new(values: Vec<Money>) -> Self {
+    Self(values)
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, Money> {
+    self.0.iter()
+  }
+
+  pub fn count(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// 合計を返す。
+  ///
+  /// 空の場合は `EmptyError`、通貨が混在している場合は `NotSameCurrencyError` を返す。
+  pub fn sum(&self) -> Result<Money, MoneyError> {
+    let currency = self.currency()?;
+    Ok(Money::new(self.0.iter().map(|e| e.amount).sum(), currency))
+  }
+
+  pub fn min(&self) -> Result<Money, MoneyError> {
+    self.currency()?;
+    Ok(self.0.iter().min_by_key(|e| e.amount).cloned().unwrap())
+  }
+
+  pub fn max(&self) -> Result<Money, MoneyError> {
+    self.currency()?;
+    Ok(self.0.iter().max_by_key(|e| e.amount).cloned().unwrap())
+  }
+
+  /// 平均を、通貨の桁数に `rounding_strategy` で丸めて返す。
+  pub fn mean(&self, rounding_strategy: RoundingStrategy) -> Result<Money, MoneyError> {
+    Ok(
+      self
+        .sum()?
+        .divided_by(Decimal::from(self.count()))
+        .rounded(rounding_strategy),
+    )
+  }
+
+  /// 中央値を返す。要素数が偶数の場合は中央の2つの平均を `rounding_strategy` で丸める。
+  pub fn median(&self, rounding_strategy: RoundingStrategy) -> Result<Money, MoneyError> {
+    let currency = self.currency()?;
+    let mut amounts = self.0.iter().map(|e| e.amount).collect::<Vec<_>>();
+    amounts.sort();
+    let middle = amounts.len() / 2;
+    let amount = if amounts.len() % 2 == 1 {
+      amounts[middle]
+    } else {
+      (amounts[middle - 1] + amounts[middle]) / Decimal::TWO
+    };
+    Ok(Money::new(amount, currency).rounded(rounding_strategy))
+  }
+
+  /// 通貨ごとに分けた集計を、通貨の出現順に返す。
+  pub fn group_by_currency(&self) -> Vec<(CurrencyCode, Tally)> {
+    let mut groups: Vec<(CurrencyCode, Tally)> = vec![];
+    for money in &self.0 {
+      match groups.iter_mut().find(|(c, _)| *c == money.currency) {
+        Some((_, tally)) => tally.0.push(money.clone()),
+        None => groups.push((money.currency, Tally(vec![money.clone()]))),
+      }
+    }
+    groups
+  }
+
+  /// 通貨ごとの合計を、通貨の出現順に返す。
+  pub fn sums_by_currency(&self) -> Vec<Money> {
+    self
+      .group_by_currency()
+      .iter()
+      .map(|(_, tally)| tally.sum().unwrap())
+      .collect()
+  }
+
+  fn currency(&self) -> Result<CurrencyCode, MoneyError> {
+    let first = self.0.first().ok_or(MoneyError::EmptyError)?;
+    if self.0.iter().any(|e| e.currency != first.currency) {
+      return Err(MoneyError::NotSameCurrencyError);
+    }
+    Ok(first.currency)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+  use rust_decimal::RoundingStrategy;
+
+  use crate::money::{Money, MoneyError, Tally};
+
+  fn usd(s: &str) -> Money {
+    Money::from((s, CurrencyCode::USD))
+  }
+
+  fn yen(amount: u32) -> Money {
+    Money::from((amount, CurrencyCode::JPY))
+  }
+
+  #[test]
+  fn test01_statistics() {
+    let tally = vec![usd("10.00"), usd("2.50"), usd("7.25"), usd("1.00")]
+      .into_iter()
+      .collect::<Tally>();
+    assert_eq!(tally.count(), 4);
+    assert_eq!(tally.sum(), Ok(usd("20.75")));
+    assert_eq!(tally.min(), Ok(usd("1.00")));
+    assert_eq!(tally.max(), Ok(usd("10.00")));
+    assert_eq!(
+      tally.mean(RoundingStrategy::MidpointAwayFromZero),
+      Ok(usd("5.19"))
+    );
+    assert_eq!(tally.mean(RoundingStrategy::ToZero), Ok(usd("5.18")));
+    assert_eq!(
+      tally.median(RoundingStrategy::MidpointNearestEven),
+      Ok(usd("4.88"))
+    );
+
+    let odd = Tally::new(vec![yen(300), yen(100), yen(200)]);
+    assert_eq!(odd.median(RoundingStrategy::ToZero), Ok(yen(200)));
+  }
+
+  #[test]
+  fn test02_errors() {
+    let empty = Tally::new(vec![]);
+    assert_eq!(empty.sum(), Err(MoneyError::EmptyError));
+    assert_eq!(
+      empty.mean(RoundingStrategy::ToZero),
+      Err(MoneyError::EmptyError)
+    );
+
+    let mixed = Tally::new(vec![usd("1"), yen(100)]);
+    assert_eq!(mixed.count(), 2);
+    assert_eq!(mixed.sum(), Err(MoneyError::NotSameCurrencyError));
+    assert_eq!(mixed.max(), Err(MoneyError::NotSameCurrencyError));
+    assert_eq!(
+      mixed.median(RoundingStrategy::ToZero),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+  }
+
+  #[test]
+  fn test03_group_by_currency() {
+    let tally = Tally::new(vec![usd("1.50"), yen(100), usd("2.25"), yen(50)]);
+    let groups = tally.group_by_currency();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0].0, CurrencyCode::USD);
+    assert_eq!(groups[0].1.count(), 2);
+    assert_eq!(groups[1].1.max(), Ok(yen(100)));
+    assert_eq!(tally.sums_by_currency(), vec![usd("3.75"), yen(150)]);
+  }
+}
@@ -0,0 +1,126 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::intervals::{Interval, LimitValue};
+use crate::money::Money;
+use crate::pricing::{OrderLine, PricingError};
+use crate::time::TimePoint;
+use crate::util::Percentage;
+
+/// 値引きの種類。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Adjustment {
+  /// 定額値引き。
+  AmountOff(Money),
+  /// 定率値引き。適用時点の合計に対して計算する。
+  PercentageOff(Percentage),
+  /// 商品 `sku` を `buy` 個買うごとに `get` 個を無料にする。
+  BuyXGetY { sku: String, buy: u32, get: u32 },
+}
+
+/// 有効期間・適用順・上限額つきの値引きルール。
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscountRule {
+  name: String,
+  adjustment: Adjustment,
+  priority: i32,
+  validity: Interval<TimePoint>,
+  cap: Option<Money>,
+}
+
+impl DiscountRule {
+  /// コンストラクタ。期間の制限なし、上限額なし、適用順 0 のルールを生成する。
+  pub fn new(name: &str, adjustment: Adjustment) -> Self {
+    Self {
+      name: name.to_string(),
+      adjustment,
+      priority: 0,
+      validity: Interval::over(LimitValue::Limitless, false, LimitValue::Limitless, false),
+      cap: None,
+    }
+  }
+
+  /// 適用順を指定する。値の小さいルールから順に適用する。
+  pub fn with_priority(mut self, priority: i32) -> Self {
+    self.priority = priority;
+    self
+  }
+
+  /// 有効期間を指定する。
+  pub fn with_validity(mut self, validity: Interval<TimePoint>) -> Self {
+    self.validity = validity;
+    self
+  }
+
+  /// 値引き額の上限を指定する。
+  pub fn with_cap(mut self, cap: Money) -> Self {
+    self.cap = Some(cap);
+    self
+  }
+
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+
+  pub fn as_adjustment(&self) -> &Adjustment {
+    &self.adjustment
+  }
+
+  pub fn to_priority(&self) -> i32 {
+    self.priority
+  }
+
+  pub fn as_validity(&self) -> &Interval<TimePoint> {
+    &self.validity
+  }
+
+  pub fn is_valid_at(&self, time_point: &TimePoint) -> bool {
+    self
+      .validity
+      .includes(&LimitValue::Limit(time_point.clone()))
+  }
+
+  /// 合計 `running_total` の注文 `lines` に対する値引き額を、上限額を考慮して正の金額で返す。
+  pub fn discount_for(
+    &self,
+    lines: &[OrderLine],
+    running_total: &Money,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<Money, PricingError> {
+    let currency = running_total.currency;
+    let discount = match &self.adjustment {
+      Adjustment::AmountOff(amount) => {
+        if amount.currency != currency {
+          return Err(PricingError::NotSameCurrencyError);
+        }
+        amount.clone()
+      }
+      Adjustment::PercentageOff(percentage) => running_total
+        .clone()
+        .times_percentage(*percentage, rounding_strategy),
+      Adjustment::BuyXGetY { sku, buy, get } => {
+        let matched = lines.iter().filter(|e| e.sku == *sku).collect::<Vec<_>>();
+        let quantity = matched
+          .iter()
+          .try_fold(0u64, |acc, e| acc.checked_add(u64::from(e.quantity)))
+          .ok_or(PricingError::QuantityOverflowError)?;
+        let bundle = u64::from(*buy) + u64::from(*get);
+        match matched
+          .iter()
+          .map(|e| &e.unit_price)
+          .min_by_key(|e| e.amount)
+        {
+          Some(unit_price) if bundle > 0 => unit_price
+            .clone()
+            .times(Decimal::from(quantity / bundle * u64::from(*get)))
+            .rounded(rounding_strategy),
+          _ => Money::zero(currency),
+        }
+      }
+    };
+    match &self.cap {
+      Some(cap) if cap.currency != currency => Err(PricingError::NotSameCurrencyError),
+      Some(cap) if discount.amount > cap.amount => Ok(cap.clone()),
+      _ => Ok(discount),
+    }
+  }
+}
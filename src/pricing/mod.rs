@@ -0,0 +1,24 @@
+mod discount_rule;
+mod pricing_schedule;
+mod pricing_tier;
+mod promotion_engine;
+
+pub use self::discount_rule::*;
+pub use self::pricing_schedule::*;
+pub use self::pricing_tier::*;
+pub use self::promotion_engine::*;
+
+#[derive(Debug, PartialEq)]
+pub enum PricingError {
+  EmptyTiersError,
+  NotSameCurrencyError,
+  /// 隣り合う価格帯の間に隙間がある。
+  TierGapError,
+  /// 価格帯が重なっている。
+  TierOverlapError,
+  /// 数量に該当する価格帯がない。
+  QuantityOutOfRangeError,
+  EmptyOrderError,
+  /// 数量の合計が表せる範囲を超えた。
+  QuantityOverflowError,
+}
@@ -0,0 +1,341 @@
+use std::cmp::Ordering;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::intervals::LimitValue;
+use crate::money::Money;
+use crate::pricing::{PricingError, PricingTier, TierQuantity};
+
+/// 価格帯の適用方法。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum PricingMethod {
+  /// 数量を価格帯ごとに分け、それぞれの単価を適用する（段階制）。
+  Graduated,
+  /// 数量全体に、その数量が該当する価格帯の単価を適用する（ボリューム制）。
+  Volume,
+}
+
+/// 価格帯ごとの請求額。
+#[derive(Debug, Clone, PartialEq)]
+pub struct TierCharge<T: TierQuantity> {
+  pub tier: PricingTier<T>,
+  pub quantity: Decimal,
+  pub amount: Money,
+}
+
+/// 価格帯ごとの内訳つきの請求額。
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceBreakdown<T: TierQuantity> {
+  pub charges: Vec<TierCharge<T>>,
+  pub total: Money,
+}
+
+/// 数量に応じた価格表。
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingSchedule<T: TierQuantity> {
+  tiers: Vec<PricingTier<T>>,
+  method: PricingMethod,
+}
+
+impl<T: TierQuantity> PricingSchedule<T> {
+  /// コンストラクタ。
+  ///
+  /// 価格帯は下限の小さい順に並べ替える。価格帯が空の場合、通貨が混在している場合、
+  /// または価格帯の間に隙間や重なりがある場合はエラーを返す。
+  pub fn new(mut tiers: Vec<PricingTier<T>>, method: PricingMethod) -> Result<Self, PricingError> {
+    let first = tiers.first().ok_or(PricingError::EmptyTiersError)?;
+    if tiers
+      .iter()
+      .any(|e| e.unit_price.currency != first.unit_price.currency)
+    {
+      return Err(PricingError::NotSameCurrencyError);
+    }
+    tiers.sort_by(|a, b| {
+      a.range
+        .lower_limit()
+        .partial_cmp(b.range.lower_limit())
+        .unwrap_or(Ordering::Equal)
+    });
+    for pair in tiers.windows(2) {
+      T::check_adjacent(&pair[0].range, &pair[1].range)?;
+    }
+    Ok(Self { tiers, method })
+  }
+
+  pub fn tiers(&self) -> &[PricingTier<T>] {
+    &self.tiers
+  }
+
+  pub fn as_method(&self) -> &PricingMethod {
+    &self.method
+  }
+
+  /// 数量 `quantity` の請求額を、価格帯ごとの金額を `rounding_strategy` で丸めて計算する。
+  ///
+  /// 数量が負の場合、またはどの価格帯にも該当しない場合はエラーを返す。
+  pub fn price(
+    &self,
+    quantity: &T,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<PriceBreakdown<T>, PricingError> {
+    if *quantity < T::default() {
+      return Err(PricingError::QuantityOutOfRangeError);
+    }
+    let value = LimitValue::Limit(quantity.clone());
+    let charges = match self.method {
+      PricingMethod::Graduated => {
+        if self.tiers[0].range.is_above(&value) || self.tiers.last().unwrap().range.is_below(&value)
+        {
+          return Err(PricingError::QuantityOutOfRangeError);
+        }
+        self
+          .tiers
+          .iter()
+          .map(|tier| (tier, T::units_within(&tier.range, quantity)))
+          .filter(|(_, units)| !units.is_zero())
+          .map(|(tier, units)| Self::charge(tier, units, rounding_strategy))
+          .collect::<Vec<_>>()
+      }
+      PricingMethod::Volume => {
+        let tier = self
+          .tiers
+          .iter()
+          .find(|e| e.range.includes(&value))
+          .ok_or(PricingError::QuantityOutOfRangeError)?;
+        vec![Self::charge(tier, quantity.to_decimal(), rounding_strategy)]
+      }
+    };
+    let currency = self.tiers[0].unit_price.currency;
+    let total = charges
+      .iter()
+      .fold(Money::zero(currency), |acc, e| acc + e.amount.clone());
+    Ok(PriceBreakdown { charges, total })
+  }
+
+  fn charge(
+    tier: &PricingTier<T>,
+    quantity: Decimal,
+    rounding_strategy: RoundingStrategy,
+  ) -> TierCharge<T> {
+    TierCharge {
+      tier: tier.clone(),
+      quantity,
+      amount: tier
+        .unit_price
+        .clone()
+        .times(quantity)
+        .rounded(rounding_strategy),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::{Decimal, RoundingStrategy};
+
+  use crate::intervals::{Interval, LimitValue};
+  use crate::money::Money;
+  use crate::pricing::{PricingError, PricingMethod, PricingSchedule, PricingTier};
+
+  fn usd(s: &str) -> Money {
+    Money::from((s, CurrencyCode::USD))
+  }
+
+  fn units(lower: u64, upper: Option<u64>, price: &str) -> PricingTier<u64> {
+    let upper = upper.map_or(LimitValue::Limitless, LimitValue::Limit);
+    PricingTier::new(
+      Interval::over(LimitValue::Limit(lower), true, upper, true),
+      usd(price),
+    )
+  }
+
+  fn usage_tiers() -> Vec<PricingTier<u64>> {
+    vec![
+      units(10_001, None, "0.05"),
+      units(1, Some(1_000), "0.10"),
+      units(1_001, Some(10_000), "0.08"),
+    ]
+  }
+
+  #[test]
+  fn test01_graduated() {
+    let schedule = PricingSchedule::new(usage_tiers(), PricingMethod::Graduated).unwrap();
+    let breakdown = schedule
+      .price(&12_500, RoundingStrategy::MidpointAwayFromZero)
+      .unwrap();
+    let quantities = breakdown
+      .charges
+      .iter()
+      .map(|e| e.quantity)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      quantities,
+      vec![
+        Decimal::from(1_000),
+        Decimal::from(9_000),
+        Decimal::from(2_500)
+      ]
+    );
+    assert_eq!(breakdown.charges[1].amount, usd("720.00"));
+    assert_eq!(breakdown.total, usd("945.00"));
+
+    let breakdown = schedule
+      .price(&500, RoundingStrategy::MidpointAwayFromZero)
+      .unwrap();
+    assert_eq!(breakdown.charges.len(), 1);
+    assert_eq!(breakdown.total, usd("50.00"));
+
+    // 最初の価格帯より少ない数量
+    assert_eq!(
+      schedule.price(&0, RoundingStrategy::MidpointAwayFromZero),
+      Err(PricingError::QuantityOutOfRangeError)
+    );
+  }
+
+  #[test]
+  fn test02_volume() {
+    let schedule = PricingSchedule::new(usage_tiers(), PricingMethod::Volume).unwrap();
+    let breakdown = schedule
+      .price(&12_500, RoundingStrategy::MidpointAwayFromZero)
+      .unwrap();
+    assert_eq!(breakdown.charges.len(), 1);
+    assert_eq!(breakdown.total, usd("625.00"));
+    assert_eq!(
+      schedule.price(&0, RoundingStrategy::MidpointAwayFromZero),
+      Err(PricingError::QuantityOutOfRangeError)
+    );
+  }
+
+  #[test]
+  fn test03_decimal_quantities() {
+    let gb = |lower: &str, upper: Option<&str>, price: &str| {
+      let upper = upper.map_or(LimitValue::Limitless, |e| {
+        LimitValue::Limit(Decimal::from_str(e).unwrap())
+      });
+      PricingTier::new(
+        Interval::over(
+          LimitValue::Limit(Decimal::from_str(lower).unwrap()),
+          true,
+          upper,
+          false,
+        ),
+        Money {
+          amount: Decimal::from_str(price).unwrap(),
+          currency: CurrencyCode::USD,
+        },
+      )
+    };
+    let schedule = PricingSchedule::new(
+      vec![gb("0", Some("50"), "0.023"), gb("50", None, "0.022")],
+      PricingMethod::Graduated,
+    )
+    .unwrap();
+    let breakdown = schedule
+      .price(
+        &Decimal::from_str("75.5").unwrap(),
+        RoundingStrategy::MidpointAwayFromZero,
+      )
+      .unwrap();
+    assert_eq!(breakdown.total, usd("1.71"));
+
+    // 数量が価格帯の境界と一致する場合、上の価格帯の数量は0になる
+    let breakdown = schedule
+      .price(&Decimal::from(50), RoundingStrategy::MidpointAwayFromZero)
+      .unwrap();
+    assert_eq!(breakdown.charges.len(), 1);
+    assert_eq!(breakdown.total, usd("1.15"));
+
+    assert_eq!(
+      PricingSchedule::new(
+        vec![gb("0", Some("50"), "0.023"), gb("60", None, "0.022")],
+        PricingMethod::Graduated,
+      ),
+      Err(PricingError::TierGapError)
+    );
+
+    // 下限のない価格帯でも、負の数量は範囲外とする
+    let unbounded = PricingTier::new(
+      Interval::under(LimitValue::Limit(Decimal::from(50))),
+      usd("0.023"),
+    );
+    for method in [PricingMethod::Graduated, PricingMethod::Volume] {
+      let schedule =
+        PricingSchedule::new(vec![unbounded.clone(), gb("50", None, "0.022")], method).unwrap();
+      assert_eq!(
+        schedule.price(&Decimal::from(-5), RoundingStrategy::MidpointAwayFromZero),
+        Err(PricingError::QuantityOutOfRangeError)
+      );
+    }
+  }
+
+  #[test]
+  fn test04_invalid_tiers() {
+    assert_eq!(
+      PricingSchedule::new(
+        vec![units(1, Some(1_000), "0.10"), units(1_002, None, "0.08")],
+        PricingMethod::Graduated,
+      ),
+      Err(PricingError::TierGapError)
+    );
+    assert_eq!(
+      PricingSchedule::new(
+        vec![units(1, Some(1_000), "0.10"), units(1_000, None, "0.08")],
+        PricingMethod::Graduated,
+      ),
+      Err(PricingError::TierOverlapError)
+    );
+    assert_eq!(
+      PricingSchedule::new(
+        vec![units(1, Some(u64::MAX), "0.10"), units(1_000, None, "0.08")],
+        PricingMethod::Graduated,
+      ),
+      Err(PricingError::TierOverlapError)
+    );
+    assert_eq!(
+      PricingSchedule::new(
+        vec![
+          PricingTier::new(
+            Interval::over(
+              LimitValue::Limit(0),
+              true,
+              LimitValue::Limit(u64::MAX),
+              false,
+            ),
+            usd("0.10"),
+          ),
+          PricingTier::new(
+            Interval::over(
+              LimitValue::Limit(u64::MAX),
+              false,
+              LimitValue::Limitless,
+              false,
+            ),
+            usd("0.08"),
+          ),
+        ],
+        PricingMethod::Graduated,
+      ),
+      Err(PricingError::TierGapError)
+    );
+    assert_eq!(
+      PricingSchedule::<u64>::new(vec![], PricingMethod::Volume),
+      Err(PricingError::EmptyTiersError)
+    );
+    assert_eq!(
+      PricingSchedule::new(
+        vec![
+          units(1, Some(1_000), "0.10"),
+          PricingTier::new(
+            Interval::over(LimitValue::Limit(1_001), true, LimitValue::Limitless, false),
+            Money::from((10u32, CurrencyCode::JPY)),
+          ),
+        ],
+        PricingMethod::Volume,
+      ),
+      Err(PricingError::NotSameCurrencyError)
+    );
+  }
+}
@@ -0,0 +1,136 @@
+use std::cmp::Ordering;
+use std::fmt::Debug;
+
+use rust_decimal::Decimal;
+
+use crate::intervals::{Interval, LimitValue};
+use crate::money::Money;
+use crate::pricing::PricingError;
+
+/// 価格帯の数量として使える型。
+pub trait TierQuantity: Debug + Default + Clone + PartialOrd {
+  fn to_decimal(&self) -> Decimal;
+
+  /// 先頭から `quantity` までのうち、区間 `interval` に含まれる数量を返す。
+  fn units_within(interval: &Interval<Self>, quantity: &Self) -> Decimal;
+
+  /// 区間 `lower` の直後に、隙間も重なりもなく区間 `upper` が続いているかを検証する。
+  fn check_adjacent(lower: &Interval<Self>, upper: &Interval<Self>) -> Result<(), PricingError>;
+}
+
+impl TierQuantity for u64 {
+  fn to_decimal(&self) -> Decimal {
+    Decimal::from(*self)
+  }
+
+  /// 1番目から `quantity` 番目までの単位のうち、区間に含まれる個数を返す。
+  fn units_within(interval: &Interval<Self>, quantity: &Self) -> Decimal {
+    let (lower, upper) = match inclusive_bounds(interval) {
+      Some(bounds) => bounds,
+      None => return Decimal::ZERO,
+    };
+    let lower = lower.max(1);
+    let upper = upper.map_or(*quantity, |e| e.min(*quantity));
+    if upper < lower {
+      Decimal::ZERO
+    } else {
+      Decimal::from(upper - lower + 1)
+    }
+  }
+
+  fn check_adjacent(lower: &Interval<Self>, upper: &Interval<Self>) -> Result<(), PricingError> {
+    let last = inclusive_bounds(lower)
+      .and_then(|(_, last)| last)
+      .ok_or(PricingError::TierOverlapError)?;
+    // 整数を含まない価格帯は、直前の価格帯との間に隙間を残す
+    let (first, _) = inclusive_bounds(upper).ok_or(PricingError::TierGapError)?;
+    // 上限が `u64::MAX` の価格帯の後には、どの価格帯も続けられない
+    let next = last.checked_add(1).ok_or(PricingError::TierOverlapError)?;
+    match first.cmp(&next) {
+      Ordering::Less => Err(PricingError::TierOverlapError),
+      Ordering::Greater => Err(PricingError::TierGapError),
+      Ordering::Equal => Ok(()),
+    }
+  }
+}
+
+/// 区間に含まれる最小と最大の整数を返す。上限がない場合、最大は `None`。
+///
+/// 下限が `u64::MAX` を含まない開区間の場合は、整数を含まないため `None` を返す。
+fn inclusive_bounds(interval: &Interval<u64>) -> Option<(u64, Option<u64>)> {
+  let lower = match interval.lower_limit() {
+    LimitValue::Limit(value) if interval.includes_lower_limit() => *value,
+    LimitValue::Limit(value) => value.checked_add(1)?,
+    LimitValue::Limitless => 0,
+  };
+  let upper = match interval.upper_limit() {
+    LimitValue::Limit(value) if interval.includes_upper_limit() => Some(*value),
+    LimitValue::Limit(value) => Some(value.saturating_sub(1)),
+    LimitValue::Limitless => None,
+  };
+  Some((lower, upper))
+}
+
+impl TierQuantity for Decimal {
+  fn to_decimal(&self) -> Decimal {
+    *self
+  }
+
+  /// 0 から `quantity` までのうち、区間に含まれる長さを返す。
+  ///
+  /// 区間が `[0, quantity]` と交わらない場合は0。端点を含むかどうかは長さには影響しない。
+  fn units_within(interval: &Interval<Self>, quantity: &Self) -> Decimal {
+    let range = Interval::closed(
+      LimitValue::Limit(Decimal::ZERO),
+      LimitValue::Limit(*quantity),
+    );
+    if !interval.intersects(&range) {
+      return Decimal::ZERO;
+    }
+    let lower = match interval.lower_limit() {
+      LimitValue::Limit(value) => (*value).max(Decimal::ZERO),
+      LimitValue::Limitless => Decimal::ZERO,
+    };
+    let upper = match interval.upper_limit() {
+      LimitValue::Limit(value) => (*value).min(*quantity),
+      LimitValue::Limitless => *quantity,
+    };
+    (upper - lower).max(Decimal::ZERO)
+  }
+
+  fn check_adjacent(lower: &Interval<Self>, upper: &Interval<Self>) -> Result<(), PricingError> {
+    match (lower.upper_limit(), upper.lower_limit()) {
+      (LimitValue::Limit(last), LimitValue::Limit(first)) => match first.cmp(last) {
+        Ordering::Less => Err(PricingError::TierOverlapError),
+        Ordering::Greater => Err(PricingError::TierGapError),
+        Ordering::Equal => match (lower.includes_upper_limit(), upper.includes_lower_limit()) {
+          (true, true) => Err(PricingError::TierOverlapError),
+          (false, false) => Err(PricingError::TierGapError),
+          _ => Ok(()),
+        },
+      },
+      _ => Err(PricingError::TierOverlapError),
+    }
+  }
+}
+
+/// 数量の範囲と単価の組。
+///
+/// 単価は通貨の桁数より細かくてもよい。請求額は価格帯ごとに丸める。
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingTier<T: TierQuantity> {
+  pub range: Interval<T>,
+  pub unit_price: Money,
+}
+
+impl<T: TierQuantity> From<(Interval<T>, Money)> for PricingTier<T> {
+  fn from((range, unit_price): (Interval<T>, Money)) -> Self {
+    Self::new(range, unit_price)
+  }
+}
+
+impl<T: TierQuantity> PricingTier<T> {
+  pub fn new(range: Interval<T>, unit_price: Money) -> Self {
+    Self { range, unit_price }
+  }
+}
@@ -0,0 +1,266 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::money::Money;
+use crate::pricing::{DiscountRule, PricingError};
+use crate::time::TimePoint;
+
+/// 注文明細。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct OrderLine {
+  pub sku: String,
+  pub unit_price: Money,
+  pub quantity: u32,
+}
+
+impl OrderLine {
+  pub fn new(sku: &str, unit_price: Money, quantity: u32) -> Self {
+    Self {
+      sku: sku.to_string(),
+      unit_price,
+      quantity,
+    }
+  }
+
+  pub fn amount(&self) -> Money {
+    self.unit_price.clone().times(Decimal::from(self.quantity))
+  }
+}
+
+/// 値引きルールを1つ適用した記録。`delta` は合計の増減（値引きは負）。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DiscountStep {
+  pub rule_name: String,
+  pub before: Money,
+  pub delta: Money,
+  pub after: Money,
+}
+
+/// 値引きの適用結果。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DiscountResult {
+  pub subtotal: Money,
+  pub steps: Vec<DiscountStep>,
+  pub total: Money,
+}
+
+impl DiscountResult {
+  /// 値引き額の合計を正の金額で返す。
+  pub fn total_discount(&self) -> Money {
+    self.subtotal.clone() - self.total.clone()
+  }
+}
+
+/// 値引きルールを適用順に重ねて適用する。
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromotionEngine {
+  rules: Vec<DiscountRule>,
+  floor: Option<Money>,
+}
+
+impl PromotionEngine {
+  /// コンストラクタ。ルールは適用順に並べ替える（同順のルールは与えた順）。
+  pub fn new(mut rules: Vec<DiscountRule>) -> Self {
+    rules.sort_by_key(|e| e.to_priority());
+    Self { rules, floor: None }
+  }
+
+  /// 値引き後の合計の下限を指定する。指定しない場合はゼロを下限とする。
+  pub fn with_floor(mut self, floor: Money) -> Self {
+    self.floor = Some(floor);
+    self
+  }
+
+  pub fn rules(&self) -> &[DiscountRule] {
+    &self.rules
+  }
+
+  /// 時点 `time_point` で有効なルールを注文 `lines` に適用する。
+  ///
+  /// 値引き額がゼロのルールは記録しない。注文が空の場合、または通貨が混在している場合はエラーを返す。
+  pub fn apply(
+    &self,
+    lines: &[OrderLine],
+    time_point: &TimePoint,
+    rounding_strategy: RoundingStrategy,
+  ) -> Result<DiscountResult, PricingError> {
+    let first = lines.first().ok_or(PricingError::EmptyOrderError)?;
+    let currency = first.unit_price.currency;
+    if lines.iter().any(|e| e.unit_price.currency != currency) {
+      return Err(PricingError::NotSameCurrencyError);
+    }
+    let floor = self.floor.clone().unwrap_or_else(|| Money::zero(currency));
+    if floor.currency != currency {
+      return Err(PricingError::NotSameCurrencyError);
+    }
+
+    let subtotal = lines
+      .iter()
+      .fold(Money::zero(currency), |acc, e| acc + e.amount())
+      .rounded(rounding_strategy);
+    let mut total = subtotal.clone();
+    let mut steps = vec![];
+    for rule in self.rules.iter().filter(|e| e.is_valid_at(time_point)) {
+      let headroom = (total.clone() - floor.clone()).amount.max(Decimal::ZERO);
+      let discount = rule
+        .discount_for(lines, &total, rounding_strategy)?
+        .amount
+        .min(headroom);
+      if discount <= Decimal::ZERO {
+        continue;
+      }
+      let delta = Money::new(-discount, currency);
+      let after = total.clone() + delta.clone();
+      steps.push(DiscountStep {
+        rule_name: rule.name().to_string(),
+        before: total,
+        delta,
+        after: after.clone(),
+      });
+      total = after;
+    }
+    Ok(DiscountResult {
+      subtotal,
+      steps,
+      total,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+  use rust_decimal::RoundingStrategy;
+
+  use crate::intervals::{Interval, LimitValue};
+  use crate::money::Money;
+  use crate::pricing::{Adjustment, DiscountRule, OrderLine, PricingError, PromotionEngine};
+  use crate::time::TimePoint;
+  use crate::util::Percentage;
+
+  fn usd(s: &str) -> Money {
+    Money::from((s, CurrencyCode::USD))
+  }
+
+  fn day(month: u32, day: u32) -> TimePoint {
+    TimePoint::at_ymd_hms_milli_utc(2024, month, day, 0, 0, 0, 0)
+  }
+
+  fn cart() -> Vec<OrderLine> {
+    vec![
+      OrderLine::new("coffee", usd("4.50"), 5),
+      OrderLine::new("bagel", usd("2.25"), 2),
+    ]
+  }
+
+  #[test]
+  fn test01_stacking_order() {
+    let engine = PromotionEngine::new(vec![
+      DiscountRule::new(
+        "10% off",
+        Adjustment::PercentageOff(Percentage::new(10.into())),
+      )
+      .with_priority(2),
+      DiscountRule::new(
+        "coffee 2+1",
+        Adjustment::BuyXGetY {
+          sku: "coffee".to_string(),
+          buy: 2,
+          get: 1,
+        },
+      )
+      .with_priority(1),
+      DiscountRule::new("coupon", Adjustment::AmountOff(usd("5"))).with_priority(3),
+    ]);
+    let result = engine
+      .apply(&cart(), &day(6, 1), RoundingStrategy::MidpointAwayFromZero)
+      .unwrap();
+
+    assert_eq!(result.subtotal, usd("27.00"));
+    let names = result
+      .steps
+      .iter()
+      .map(|e| e.rule_name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(names, vec!["coffee 2+1", "10% off", "coupon"]);
+    assert_eq!(result.steps[0].delta, usd("-4.50"));
+    assert_eq!(result.steps[1].before, usd("22.50"));
+    assert_eq!(result.steps[1].delta, usd("-2.25"));
+    assert_eq!(result.total, usd("15.25"));
+    assert_eq!(result.total_discount(), usd("11.75"));
+  }
+
+  #[test]
+  fn test02_cap_floor_and_validity() {
+    let summer = Interval::over(
+      LimitValue::Limit(day(7, 1)),
+      true,
+      LimitValue::Limit(day(9, 1)),
+      false,
+    );
+    let engine = PromotionEngine::new(vec![
+      DiscountRule::new(
+        "half off",
+        Adjustment::PercentageOff(Percentage::new(50.into())),
+      )
+      .with_cap(usd("10")),
+      DiscountRule::new("summer", Adjustment::AmountOff(usd("10"))).with_validity(summer),
+    ])
+    .with_floor(usd("12"));
+
+    let june = engine
+      .apply(&cart(), &day(6, 1), RoundingStrategy::MidpointAwayFromZero)
+      .unwrap();
+    assert_eq!(june.steps.len(), 1);
+    assert_eq!(june.steps[0].delta, usd("-10.00"));
+    assert_eq!(june.total, usd("17.00"));
+
+    let july = engine
+      .apply(&cart(), &day(7, 1), RoundingStrategy::MidpointAwayFromZero)
+      .unwrap();
+    assert_eq!(july.steps[1].delta, usd("-5.00"));
+    assert_eq!(july.total, usd("12.00"));
+  }
+
+  #[test]
+  fn test03_errors() {
+    let engine = PromotionEngine::new(vec![DiscountRule::new(
+      "yen coupon",
+      Adjustment::AmountOff(Money::from((500u32, CurrencyCode::JPY))),
+    )]);
+    assert_eq!(
+      engine.apply(&cart(), &day(6, 1), RoundingStrategy::ToZero),
+      Err(PricingError::NotSameCurrencyError)
+    );
+    assert_eq!(
+      engine.apply(&[], &day(6, 1), RoundingStrategy::ToZero),
+      Err(PricingError::EmptyOrderError)
+    );
+  }
+
+  #[test]
+  fn test04_large_quantities() {
+    let rule = |buy: u32, get: u32| {
+      DiscountRule::new(
+        "bulk",
+        Adjustment::BuyXGetY {
+          sku: "coffee".to_string(),
+          buy,
+          get,
+        },
+      )
+    };
+    let lines = vec![
+      OrderLine::new("coffee", usd("1"), u32::MAX),
+      OrderLine::new("coffee", usd("1"), u32::MAX),
+    ];
+    let subtotal = usd("8589934590");
+    assert_eq!(
+      rule(u32::MAX, 1).discount_for(&lines, &subtotal, RoundingStrategy::ToZero),
+      Ok(usd("1"))
+    );
+    assert_eq!(
+      rule(1, u32::MAX).discount_for(&lines, &subtotal, RoundingStrategy::ToZero),
+      Ok(usd("4294967295"))
+    );
+  }
+}
@@ -0,0 +1,211 @@
+//! `serde` フィーチャーが有効な場合のシリアライズ実装。
+
+use std::hash::Hash;
+use std::str::FromStr;
+
+use iso_4217::CurrencyCode;
+use rust_decimal::Decimal;
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::money::{Allotment, Allotments, Money, MoneyFan};
+use crate::util::Ratio;
+
+#[derive(Deserialize)]
+struct MoneyRepr {
+  amount: String,
+  currency: String,
+}
+
+#[derive(Deserialize)]
+struct RatioRepr {
+  numerator: String,
+  denominator: String,
+}
+
+fn parse_decimal<E: Error>(value: &str) -> Result<Decimal, E> {
+  Decimal::from_str(value).map_err(|_| E::custom(format!("invalid decimal: {}", value)))
+}
+
+/// 金額は精度を失わないよう文字列で表す。例: `{"amount":"12.34","currency":"USD"}`
+impl Serialize for Money {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut state = serializer.serialize_struct("Money", 2)?;
+    state.serialize_field("amount", &self.amount.to_string())?;
+    state.serialize_field("currency", self.currency.alpha())?;
+    state.end()
+  }
+}
+
+/// 通貨コードを検証し、`Money::new` と同様に通貨の桁数に合わせる。
+/// 補助単位の桁数が定義されていない通貨（例: `XAU`）はエラーを返す。
+impl<'de> Deserialize<'de> for Money {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let repr = MoneyRepr::deserialize(deserializer)?;
+    let amount = parse_decimal(&repr.amount)?;
+    let currency = CurrencyCode::from_str(&repr.currency)
+      .map_err(|_| D::Error::custom(format!("unknown currency: {}", repr.currency)))?;
+    if currency.digit().is_none() {
+      return Err(D::Error::custom(format!(
+        "currency without minor unit: {}",
+        repr.currency
+      )));
+    }
+    Ok(Money::new(amount, currency))
+  }
+}
+
+impl Serialize for Ratio {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut state = serializer.serialize_struct("Ratio", 2)?;
+    state.serialize_field("numerator", &self.to_numerator().to_string())?;
+    state.serialize_field("denominator", &self.to_denominator().to_string())?;
+    state.end()
+  }
+}
+
+/// 分母がゼロの場合はエラーを返す。
+impl<'de> Deserialize<'de> for Ratio {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let repr = RatioRepr::deserialize(deserializer)?;
+    let numerator = parse_decimal(&repr.numerator)?;
+    let denominator = parse_decimal::<D::Error>(&repr.denominator)?;
+    if denominator.is_zero() {
+      return Err(D::Error::custom("denominator is zero"));
+    }
+    Ok(Ratio::new(numerator, denominator))
+  }
+}
+
+/// [Allotment] の配列として表す。
+impl<T: Eq + Hash + Serialize> Serialize for Allotments<T> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_seq(self.0.values())
+  }
+}
+
+/// 同じエンティティへの割り当ては合算する。通貨が異なる場合はエラーを返す。
+impl<'de, T: Clone + Eq + Hash + Deserialize<'de>> Deserialize<'de> for Allotments<T> {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let values = Vec::<Allotment<T>>::deserialize(deserializer)?;
+    Allotments::new(values).map_err(|err| D::Error::custom(format!("{:?}", err)))
+  }
+}
+
+impl<T: Eq + Hash + Serialize> Serialize for MoneyFan<T> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    self.0.serialize(serializer)
+  }
+}
+
+impl<'de, T: Clone + Eq + Hash + Deserialize<'de>> Deserialize<'de> for MoneyFan<T> {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Allotments::deserialize(deserializer).map(MoneyFan::new)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+  use rust_decimal::Decimal;
+
+  use crate::money::{Allotment, Allotments, Money, MoneyFan};
+  use crate::util::Ratio;
+
+  fn usd(s: &str) -> Money {
+    Money::from((s, CurrencyCode::USD))
+  }
+
+  #[test]
+  fn test01_money() {
+    let json = serde_json::to_string(&usd("12.34")).unwrap();
+    assert_eq!(json, r#"{"amount":"12.34","currency":"USD"}"#);
+    assert_eq!(serde_json::from_str::<Money>(&json).unwrap(), usd("12.34"));
+
+    let rescaled: Money = serde_json::from_str(r#"{"amount":"100","currency":"JPY"}"#).unwrap();
+    assert_eq!(rescaled, Money::from((100u32, CurrencyCode::JPY)));
+    let rescaled: Money = serde_json::from_str(r#"{"amount":"1.5","currency":"USD"}"#).unwrap();
+    assert_eq!(rescaled.amount.to_string(), "1.50");
+
+    assert!(serde_json::from_str::<Money>(r#"{"amount":"1","currency":"XYZ"}"#).is_err());
+    assert!(serde_json::from_str::<Money>(r#"{"amount":"abc","currency":"USD"}"#).is_err());
+    assert!(serde_json::from_str::<Money>(r#"{"amount":"1","currency":"XAU"}"#).is_err());
+  }
+
+  #[test]
+  fn test02_ratio() {
+    let ratio = Ratio::new_i64(1, 3);
+    let json = serde_json::to_string(&ratio).unwrap();
+    assert_eq!(json, r#"{"numerator":"1","denominator":"3"}"#);
+    assert_eq!(serde_json::from_str::<Ratio>(&json).unwrap(), ratio);
+    assert!(serde_json::from_str::<Ratio>(r#"{"numerator":"1","denominator":"0"}"#).is_err());
+  }
+
+  #[test]
+  fn test03_allotments() {
+    let allotment = Allotment::new("alice".to_string(), usd("10"));
+    let json = serde_json::to_string(&allotment).unwrap();
+    assert_eq!(
+      json,
+      r#"{"entity":"alice","amount":{"amount":"10.00","currency":"USD"}}"#
+    );
+
+    let fan = MoneyFan::new(
+      Allotments::new(vec![
+        Allotment::new("alice".to_string(), usd("10")),
+        Allotment::new("bob".to_string(), usd("5.25")),
+      ])
+      .unwrap(),
+    );
+    let json = serde_json::to_string(&fan).unwrap();
+    assert_eq!(
+      serde_json::from_str::<MoneyFan<String>>(&json).unwrap(),
+      fan
+    );
+
+    let merged: Allotments<String> = serde_json::from_str(
+      r#"[
+        {"entity":"alice","amount":{"amount":"1","currency":"USD"}},
+        {"entity":"alice","amount":{"amount":"2","currency":"USD"}}
+      ]"#,
+    )
+    .unwrap();
+    assert_eq!(
+      merged.get(&"alice".to_string()).unwrap().amount.amount,
+      Decimal::from(3)
+    );
+
+    let mixed = serde_json::from_str::<Allotments<String>>(
+      r#"[
+        {"entity":"alice","amount":{"amount":"1","currency":"USD"}},
+        {"entity":"alice","amount":{"amount":"2","currency":"JPY"}}
+      ]"#,
+    );
+    assert!(mixed.is_err());
+  }
+}
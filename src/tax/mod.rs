@@ -0,0 +1,15 @@
+mod tax_breakdown;
+mod tax_calculator;
+mod tax_category;
+mod tax_rate_schedule;
+
+pub use self::tax_breakdown::*;
+pub use self::tax_calculator::*;
+pub use self::tax_category::*;
+pub use self::tax_rate_schedule::*;
+
+#[derive(Debug, PartialEq)]
+pub enum TaxError {
+  RateNotFoundError,
+  NotSameCurrencyError,
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use crate::money::{Money, MoneyError, MoneyFan};
+use crate::tax::TaxCategory;
+use crate::util::Percentage;
+
+/// 税区分ごとの税抜金額・税額の内訳。
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaxBreakdown {
+  net: MoneyFan<TaxCategory>,
+  tax: MoneyFan<TaxCategory>,
+  rates: HashMap<TaxCategory, Percentage>,
+}
+
+impl TaxBreakdown {
+  pub fn new(
+    net: MoneyFan<TaxCategory>,
+    tax: MoneyFan<TaxCategory>,
+    rates: HashMap<TaxCategory, Percentage>,
+  ) -> Self {
+    Self { net, tax, rates }
+  }
+
+  /// 税区分ごとの税抜金額。
+  pub fn as_net(&self) -> &MoneyFan<TaxCategory> {
+    &self.net
+  }
+
+  /// 税区分ごとの税額。
+  pub fn as_tax(&self) -> &MoneyFan<TaxCategory> {
+    &self.tax
+  }
+
+  /// 税区分ごとの税込金額。
+  pub fn to_gross(&self) -> MoneyFan<TaxCategory> {
+    self
+      .net
+      .clone()
+      .merge(self.tax.clone(), |net, tax| net + tax)
+  }
+
+  pub fn rate_of(&self, category: &TaxCategory) -> Option<Percentage> {
+    self.rates.get(category).cloned()
+  }
+
+  pub fn net_of(&self, category: &TaxCategory) -> Option<&Money> {
+    self.net.amount_of(category)
+  }
+
+  pub fn tax_of(&self, category: &TaxCategory) -> Option<&Money> {
+    self.tax.amount_of(category)
+  }
+
+  pub fn net_total(&self) -> Result<Money, MoneyError> {
+    self.net.total()
+  }
+
+  pub fn tax_total(&self) -> Result<Money, MoneyError> {
+    self.tax.total()
+  }
+
+  pub fn gross_total(&self) -> Result<Money, MoneyError> {
+    self.net_total()?.add(self.tax_total()?)
+  }
+}
@@ -0,0 +1,338 @@
+use std::collections::HashMap;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use rust_fp_categories::Empty;
+
+use crate::money::{Allotment, Allotments, Money, MoneyFan};
+use crate::tax::{TaxBreakdown, TaxCategory, TaxError, TaxRateSchedule};
+use crate::time::CalendarDate;
+use crate::util::Percentage;
+
+/// 明細金額が税抜か税込か。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TaxMode {
+  Exclusive,
+  Inclusive,
+}
+
+/// 税額の端数処理を行う単位。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TaxRounding {
+  /// 明細ごとに税額を計算して丸め、税区分ごとに合計する。
+  PerLine,
+  /// 税区分ごとに明細金額を合計してから、税額を一度だけ計算して丸める。
+  PerInvoice,
+}
+
+/// 課税対象の明細。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TaxableLine {
+  pub category: TaxCategory,
+  pub amount: Money,
+}
+
+impl TaxableLine {
+  pub fn new(category: TaxCategory, amount: Money) -> Self {
+    Self { category, amount }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct TaxCalculator {
+  schedule: TaxRateSchedule,
+  mode: TaxMode,
+  rounding: TaxRounding,
+  rounding_strategy: RoundingStrategy,
+}
+
+impl TaxCalculator {
+  pub fn new(
+    schedule: TaxRateSchedule,
+    mode: TaxMode,
+    rounding: TaxRounding,
+    rounding_strategy: RoundingStrategy,
+  ) -> Self {
+    Self {
+      schedule,
+      mode,
+      rounding,
+      rounding_strategy,
+    }
+  }
+
+  /// 税抜金額 `net` に対する税額を返す。
+  pub fn tax_for_exclusive(
+    net: Money,
+    rate: Percentage,
+    rounding_strategy: RoundingStrategy,
+  ) -> Money {
+    net.times_percentage(rate, rounding_strategy)
+  }
+
+  /// 税込金額 `gross` に含まれる税額を返す。
+  pub fn tax_for_inclusive(
+    gross: Money,
+    rate: Percentage,
+    rounding_strategy: RoundingStrategy,
+  ) -> Money {
+    let factor = rate.to_factor();
+    gross
+      .times(factor)
+      .divided_by(factor + Decimal::ONE)
+      .rounded(rounding_strategy)
+  }
+
+  /// 税抜金額を税込金額に変換する。
+  pub fn to_inclusive(net: Money, rate: Percentage, rounding_strategy: RoundingStrategy) -> Money {
+    let tax = Self::tax_for_exclusive(net.clone(), rate, rounding_strategy);
+    net + tax
+  }
+
+  /// 税込金額を税抜金額に変換する。
+  pub fn to_exclusive(
+    gross: Money,
+    rate: Percentage,
+    rounding_strategy: RoundingStrategy,
+  ) -> Money {
+    let tax = Self::tax_for_inclusive(gross.clone(), rate, rounding_strategy);
+    gross - tax
+  }
+
+  /// `date` 時点の税率で、明細 `lines` の税額を税区分ごとに計算する。
+  ///
+  /// 税率が見つからない税区分がある場合、または明細の通貨が混在している場合はエラーを返す。
+  pub fn calculate(
+    &self,
+    date: &CalendarDate,
+    lines: &[TaxableLine],
+  ) -> Result<TaxBreakdown, TaxError> {
+    if let Some(first) = lines.first() {
+      if lines
+        .iter()
+        .any(|e| e.amount.currency != first.amount.currency)
+      {
+        return Err(TaxError::NotSameCurrencyError);
+      }
+    }
+
+    let mut rates = HashMap::new();
+    for line in lines {
+      let rate = self
+        .schedule
+        .rate_on(&line.category, date)
+        .ok_or(TaxError::RateNotFoundError)?;
+      rates.insert(line.category.clone(), rate);
+    }
+
+    let amounts = match self.rounding {
+      TaxRounding::PerLine => lines
+        .iter()
+        .map(|e| Allotment::new(e.category.clone(), e.amount.clone()))
+        .collect::<Vec<_>>(),
+      TaxRounding::PerInvoice => Allotments::new(
+        lines
+          .iter()
+          .map(|e| Allotment::new(e.category.clone(), e.amount.clone())),
+      )
+      .map_err(|_| TaxError::NotSameCurrencyError)?
+      .into_iter()
+      .collect(),
+    };
+
+    let mut net = Allotments::empty();
+    let mut tax = Allotments::empty();
+    for allotment in amounts {
+      let rate = rates[&allotment.entity];
+      let (net_amount, tax_amount) = match self.mode {
+        TaxMode::Exclusive => {
+          let tax_amount =
+            Self::tax_for_exclusive(allotment.amount.clone(), rate, self.rounding_strategy);
+          (allotment.amount, tax_amount)
+        }
+        TaxMode::Inclusive => {
+          let tax_amount =
+            Self::tax_for_inclusive(allotment.amount.clone(), rate, self.rounding_strategy);
+          (allotment.amount - tax_amount.clone(), tax_amount)
+        }
+      };
+      net
+        .insert(Allotment::new(allotment.entity.clone(), net_amount))
+        .map_err(|_| TaxError::NotSameCurrencyError)?;
+      tax
+        .insert(Allotment::new(allotment.entity, tax_amount))
+        .map_err(|_| TaxError::NotSameCurrencyError)?;
+    }
+
+    Ok(TaxBreakdown::new(
+      MoneyFan::new(net),
+      MoneyFan::new(tax),
+      rates,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use iso_4217::CurrencyCode;
+  use rust_decimal::RoundingStrategy;
+  use std::str::FromStr;
+
+  use crate::money::{Money, MoneyError};
+  use crate::tax::{
+    TaxCalculator, TaxCategory, TaxError, TaxMode, TaxRateSchedule, TaxRounding, TaxableLine,
+  };
+  use crate::time::CalendarDate;
+  use crate::util::Percentage;
+
+  fn yen(amount: u32) -> Money {
+    Money::from((amount, CurrencyCode::JPY))
+  }
+
+  fn lines() -> Vec<TaxableLine> {
+    let standard = TaxCategory::new("standard");
+    let reduced = TaxCategory::new("reduced");
+    vec![
+      TaxableLine::new(standard.clone(), yen(1980)),
+      TaxableLine::new(standard, yen(2480)),
+      TaxableLine::new(reduced.clone(), yen(398)),
+      TaxableLine::new(reduced, yen(598)),
+    ]
+  }
+
+  fn calculator(mode: TaxMode, rounding: TaxRounding) -> TaxCalculator {
+    TaxCalculator::new(
+      TaxRateSchedule::japanese_consumption_tax(),
+      mode,
+      rounding,
+      RoundingStrategy::ToZero,
+    )
+  }
+
+  #[test]
+  fn test01_conversion() {
+    let rate = Percentage::new(10.into());
+    assert_eq!(
+      TaxCalculator::to_inclusive(yen(1980), rate, RoundingStrategy::ToZero),
+      yen(2178)
+    );
+    assert_eq!(
+      TaxCalculator::to_exclusive(yen(2178), rate, RoundingStrategy::ToZero),
+      yen(1980)
+    );
+    assert_eq!(
+      TaxCalculator::tax_for_inclusive(
+        yen(1080),
+        Percentage::new(8.into()),
+        RoundingStrategy::ToZero
+      ),
+      yen(80)
+    );
+  }
+
+  #[test]
+  fn test02_exclusive_per_invoice() {
+    let date = CalendarDate::from((2023, 10, 1));
+    let breakdown = calculator(TaxMode::Exclusive, TaxRounding::PerInvoice)
+      .calculate(&date, &lines())
+      .unwrap();
+    let standard = TaxCategory::new("standard");
+    let reduced = TaxCategory::new("reduced");
+
+    assert_eq!(breakdown.net_of(&standard), Some(&yen(4460)));
+    assert_eq!(breakdown.tax_of(&standard), Some(&yen(446)));
+    assert_eq!(breakdown.net_of(&reduced), Some(&yen(996)));
+    assert_eq!(breakdown.tax_of(&reduced), Some(&yen(79)));
+    assert_eq!(breakdown.rate_of(&reduced), Some(Percentage::new(8.into())));
+    assert_eq!(breakdown.tax_total(), Ok(yen(525)));
+    assert_eq!(breakdown.gross_total(), Ok(yen(5981)));
+    assert_eq!(breakdown.to_gross().amount_of(&reduced), Some(&yen(1075)));
+  }
+
+  #[test]
+  fn test03_exclusive_per_line() {
+    let date = CalendarDate::from((2023, 10, 1));
+    let breakdown = calculator(TaxMode::Exclusive, TaxRounding::PerLine)
+      .calculate(&date, &lines())
+      .unwrap();
+    assert_eq!(
+      breakdown.tax_of(&TaxCategory::new("reduced")),
+      Some(&yen(78))
+    );
+    assert_eq!(breakdown.tax_total(), Ok(yen(524)));
+  }
+
+  #[test]
+  fn test04_inclusive() {
+    let date = CalendarDate::from((2023, 10, 1));
+    let breakdown = calculator(TaxMode::Inclusive, TaxRounding::PerInvoice)
+      .calculate(&date, &lines())
+      .unwrap();
+    let standard = TaxCategory::new("standard");
+    assert_eq!(breakdown.tax_of(&standard), Some(&yen(405)));
+    assert_eq!(breakdown.net_of(&standard), Some(&yen(4055)));
+    assert_eq!(breakdown.gross_total(), Ok(yen(5456)));
+  }
+
+  #[test]
+  fn test05_rate_depends_on_date() {
+    let breakdown = calculator(TaxMode::Exclusive, TaxRounding::PerInvoice)
+      .calculate(
+        &CalendarDate::from((2019, 9, 30)),
+        &[TaxableLine::new(TaxCategory::new("standard"), yen(1000))],
+      )
+      .unwrap();
+    assert_eq!(breakdown.tax_total(), Ok(yen(80)));
+
+    assert_eq!(
+      calculator(TaxMode::Exclusive, TaxRounding::PerInvoice)
+        .calculate(&CalendarDate::from((2019, 9, 30)), &lines()),
+      Err(TaxError::RateNotFoundError)
+    );
+  }
+
+  #[test]
+  fn test06_sales_tax() {
+    let california = TaxCategory::new("US-CA");
+    let schedule = TaxRateSchedule::new().with_rate(
+      california.clone(),
+      CalendarDate::from((2017, 1, 1)),
+      Percentage::from_str("7.25%").unwrap(),
+    );
+    let calculator = TaxCalculator::new(
+      schedule,
+      TaxMode::Exclusive,
+      TaxRounding::PerInvoice,
+      RoundingStrategy::MidpointAwayFromZero,
+    );
+    let usd = |s: &str| Money::from((s, CurrencyCode::USD));
+    let breakdown = calculator
+      .calculate(
+        &CalendarDate::from((2024, 5, 1)),
+        &[
+          TaxableLine::new(california.clone(), usd("19.99")),
+          TaxableLine::new(california.clone(), usd("5.49")),
+        ],
+      )
+      .unwrap();
+    assert_eq!(breakdown.tax_of(&california), Some(&usd("1.85")));
+    assert_eq!(breakdown.gross_total(), Ok(usd("27.33")));
+
+    assert_eq!(
+      calculator.calculate(
+        &CalendarDate::from((2024, 5, 1)),
+        &[
+          TaxableLine::new(california.clone(), usd("1")),
+          TaxableLine::new(california, yen(1)),
+        ],
+      ),
+      Err(TaxError::NotSameCurrencyError)
+    );
+    assert_eq!(
+      calculator
+        .calculate(&CalendarDate::from((2024, 5, 1)), &[])
+        .unwrap()
+        .tax_total(),
+      Err(MoneyError::EmptyError)
+    );
+  }
+}
@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// 税区分（例: 標準税率、軽減税率、州の売上税）。
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TaxCategory(String);
+
+impl fmt::Display for TaxCategory {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.0)
+  }
+}
+
+impl From<&str> for TaxCategory {
+  fn from(name: &str) -> Self {
+    Self::new(name)
+  }
+}
+
+impl TaxCategory {
+  pub fn new(name: &str) -> Self {
+    Self(name.to_string())
+  }
+
+  pub fn name(&self) -> &str {
+    &self.0
+  }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use crate::tax::TaxCategory;
+use crate::time::CalendarDate;
+use crate::util::Percentage;
+
+/// 税区分ごとの税率を、施行日つきで保持する。
+///
+/// ある日付に適用される税率は、その日以前に施行された税率のうち最も新しいもの。
+#[derive(Debug, Clone, Default)]
+pub struct TaxRateSchedule(HashMap<TaxCategory, Vec<(CalendarDate, Percentage)>>);
+
+impl TaxRateSchedule {
+  pub fn new() -> Self {
+    Self(HashMap::new())
+  }
+
+  /// `effective_from` から `category` に税率 `rate` を適用する。
+  pub fn with_rate(
+    mut self,
+    category: TaxCategory,
+    effective_from: CalendarDate,
+    rate: Percentage,
+  ) -> Self {
+    let rates = self.0.entry(category).or_default();
+    rates.retain(|(date, _)| *date != effective_from);
+    rates.push((effective_from, rate));
+    rates.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+    self
+  }
+
+  /// `date` に `category` へ適用される税率を返す。
+  pub fn rate_on(&self, category: &TaxCategory, date: &CalendarDate) -> Option<Percentage> {
+    self
+      .0
+      .get(category)?
+      .iter()
+      .rev()
+      .find(|(effective_from, _)| !date.is_before(effective_from))
+      .map(|(_, rate)| *rate)
+  }
+
+  pub fn categories(&self) -> impl Iterator<Item = &TaxCategory> {
+    self.0.keys()
+  }
+
+  /// 日本の消費税（標準税率・軽減税率）。
+  pub fn japanese_consumption_tax() -> Self {
+    let standard = TaxCategory::new("standard");
+    let reduced = TaxCategory::new("reduced");
+    Self::new()
+      .with_rate(
+        standard.clone(),
+        CalendarDate::from((1989, 4, 1)),
+        Percentage::new(3.into()),
+      )
+      .with_rate(
+        standard.clone(),
+        CalendarDate::from((1997, 4, 1)),
+        Percentage::new(5.into()),
+      )
+      .with_rate(
+        standard.clone(),
+        CalendarDate::from((2014, 4, 1)),
+        Percentage::new(8.into()),
+      )
+      .with_rate(
+        standard,
+        CalendarDate::from((2019, 10, 1)),
+        Percentage::new(10.into()),
+      )
+      .with_rate(
+        reduced,
+        CalendarDate::from((2019, 10, 1)),
+        Percentage::new(8.into()),
+      )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::tax::{TaxCategory, TaxRateSchedule};
+  use crate::time::CalendarDate;
+  use crate::util::Percentage;
+
+  #[test]
+  fn test01_rate_on() {
+    let schedule = TaxRateSchedule::japanese_consumption_tax();
+    let standard = TaxCategory::new("standard");
+    let reduced = TaxCategory::new("reduced");
+
+    assert_eq!(
+      schedule.rate_on(&standard, &CalendarDate::from((2019, 9, 30))),
+      Some(Percentage::new(8.into()))
+    );
+    assert_eq!(
+      schedule.rate_on(&standard, &CalendarDate::from((2019, 10, 1))),
+      Some(Percentage::new(10.into()))
+    );
+    assert_eq!(
+      schedule.rate_on(&standard, &CalendarDate::from((1989, 3, 31))),
+      None
+    );
+    assert_eq!(
+      schedule.rate_on(&reduced, &CalendarDate::from((2019, 9, 30))),
+      None
+    );
+    assert_eq!(
+      schedule.rate_on(&TaxCategory::new("vat"), &CalendarDate::from((2020, 1, 1))),
+      None
+    );
+  }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+use std::ops::RangeInclusive;
+
+use chrono::Utc;
+
+use crate::time::{CalendarDate, DayOfWeek, HolidayProvider};
+
+/// 休日に当たった日付を営業日に寄せる規則。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum BusinessDayConvention {
+  /// 翌営業日。
+  Following,
+  /// 翌営業日。ただし月をまたぐ場合は前営業日。
+  ModifiedFollowing,
+  /// 前営業日。
+  Preceding,
+}
+
+/// 週末と休日から営業日を判定する暦。
+#[derive(Debug, Clone, PartialEq)]
+pub struct BusinessCalendar {
+  weekends: HashSet<DayOfWeek>,
+  holidays: HashSet<CalendarDate>,
+}
+
+impl Default for BusinessCalendar {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl BusinessCalendar {
+  /// コンストラクタ。土曜日と日曜日を週末とし、休日は持たない。
+  pub fn new() -> Self {
+    Self {
+      weekends: [DayOfWeek::Saturday, DayOfWeek::Sunday]
+        .iter()
+        .cloned()
+        .collect(),
+      holidays: HashSet::new(),
+    }
+  }
+
+  /// 週末の曜日を指定する。すべての曜日を週末にした場合はパニックする。
+  pub fn with_weekends<I>(mut self, weekends: I) -> Self
+  where
+    I: IntoIterator<Item = DayOfWeek>,
+  {
+    self.weekends = weekends.into_iter().collect();
+    if self.weekends.len() >= 7 {
+      panic!("a business calendar needs at least one working day of the week")
+    }
+    self
+  }
+
+  /// 休日を追加する。
+  pub fn with_holiday(mut self, date: CalendarDate) -> Self {
+    self.holidays.insert(date);
+    self
+  }
+
+  /// 休日をまとめて追加する。
+  pub fn with_holidays<I>(mut self, dates: I) -> Self
+  where
+    I: IntoIterator<Item = CalendarDate>,
+  {
+    self.holidays.extend(dates);
+    self
+  }
+
+  /// 休日の提供元 `provider` から、`years` の各年の休日を追加する。
+  pub fn with_holidays_from<P>(self, provider: &P, years: RangeInclusive<i32>) -> Self
+  where
+    P: HolidayProvider,
+  {
+    let dates = years
+      .flat_map(|year| provider.holidays_in(year))
+      .map(|e| e.date)
+      .collect::<Vec<_>>();
+    self.with_holidays(dates)
+  }
+
+  pub fn as_weekends(&self) -> &HashSet<DayOfWeek> {
+    &self.weekends
+  }
+
+  pub fn as_holidays(&self) -> &HashSet<CalendarDate> {
+    &self.holidays
+  }
+
+  pub fn is_weekend(&self, date: &CalendarDate) -> bool {
+    self.weekends.contains(&date.day_of_week_at_utc())
+  }
+
+  pub fn is_holiday(&self, date: &CalendarDate) -> bool {
+    self.holidays.contains(date)
+  }
+
+  pub fn is_business_day(&self, date: &CalendarDate) -> bool {
+    !self.is_weekend(date) && !self.is_holiday(date)
+  }
+
+  /// `date` より後の最初の営業日を返す。
+  pub fn next_business_day(&self, date: &CalendarDate) -> CalendarDate {
+    self.step(date, 1)
+  }
+
+  /// `date` より前の最初の営業日を返す。
+  pub fn previous_business_day(&self, date: &CalendarDate) -> CalendarDate {
+    self.step(date, -1)
+  }
+
+  /// `date` から `days` 営業日後の日付を返す。`days` が負の場合は前に数える。
+  ///
+  /// `date` 自体は数えない。`days` がゼロの場合は `date` をそのまま返す。
+  pub fn plus_business_days(&self, date: &CalendarDate, days: i64) -> CalendarDate {
+    let direction = if days < 0 { -1 } else { 1 };
+    (0..days.abs()).fold(date.clone(), |acc, _| self.step(&acc, direction))
+  }
+
+  /// `from` 以上 `to` 未満の営業日の数を返す。`to` が `from` より前の場合は負の数を返す。
+  pub fn business_days_between(&self, from: &CalendarDate, to: &CalendarDate) -> i64 {
+    if to.is_before(from) {
+      return -self.business_days_between(to, from);
+    }
+    let mut count = 0;
+    let mut date = from.clone();
+    while date.is_before(to) {
+      if self.is_business_day(&date) {
+        count += 1;
+      }
+      date = date.add_days(1, Utc);
+    }
+    count
+  }
+
+  /// `date` が営業日でない場合に、規則 `convention` に従って営業日に寄せる。
+  pub fn roll(&self, date: &CalendarDate, convention: BusinessDayConvention) -> CalendarDate {
+    if self.is_business_day(date) {
+      return date.clone();
+    }
+    match convention {
+      BusinessDayConvention::Following => self.next_business_day(date),
+      BusinessDayConvention::Preceding => self.previous_business_day(date),
+      BusinessDayConvention::ModifiedFollowing => {
+        let following = self.next_business_day(date);
+        if following.as_year_month() == date.as_year_month() {
+          following
+        } else {
+          self.previous_business_day(date)
+        }
+      }
+    }
+  }
+
+  fn step(&self, date: &CalendarDate, direction: i64) -> CalendarDate {
+    let mut result = date.add_days(direction, Utc);
+    while !self.is_business_day(&result) {
+      result = result.add_days(direction, Utc);
+    }
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::time::{BusinessCalendar, BusinessDayConvention, CalendarDate, DayOfWeek};
+
+  fn date(month: u32, day: u32) -> CalendarDate {
+    CalendarDate::from((2024, month, day))
+  }
+
+  fn calendar() -> BusinessCalendar {
+    // 2024-05-03(金) から 2024-05-06(月) まで休み
+    BusinessCalendar::new().with_holidays(vec![date(5, 3), date(5, 6)])
+  }
+
+  #[test]
+  fn test01_is_business_day() {
+    let calendar = calendar();
+    assert!(calendar.is_business_day(&date(5, 2)));
+    assert!(!calendar.is_business_day(&date(5, 3)));
+    assert!(!calendar.is_business_day(&date(5, 4)));
+    assert_eq!(calendar.next_business_day(&date(5, 2)), date(5, 7));
+    assert_eq!(calendar.previous_business_day(&date(5, 7)), date(5, 2));
+
+    let middle_east =
+      BusinessCalendar::new().with_weekends(vec![DayOfWeek::Friday, DayOfWeek::Saturday]);
+    assert!(middle_east.is_business_day(&date(5, 5)));
+    assert!(!middle_east.is_business_day(&date(5, 3)));
+  }
+
+  #[test]
+  fn test02_business_day_arithmetic() {
+    let calendar = calendar();
+    assert_eq!(calendar.plus_business_days(&date(5, 1), 2), date(5, 7));
+    assert_eq!(calendar.plus_business_days(&date(5, 7), -1), date(5, 2));
+    assert_eq!(calendar.plus_business_days(&date(5, 4), 0), date(5, 4));
+
+    assert_eq!(calendar.business_days_between(&date(5, 1), &date(5, 8)), 3);
+    assert_eq!(calendar.business_days_between(&date(5, 8), &date(5, 1)), -3);
+    assert_eq!(calendar.business_days_between(&date(5, 1), &date(5, 1)), 0);
+  }
+
+  #[test]
+  fn test03_roll() {
+    let calendar = BusinessCalendar::new();
+    // 2024-08-31 は土曜日
+    let saturday = date(8, 31);
+    assert_eq!(
+      calendar.roll(&saturday, BusinessDayConvention::Following),
+      date(9, 2)
+    );
+    assert_eq!(
+      calendar.roll(&saturday, BusinessDayConvention::ModifiedFollowing),
+      date(8, 30)
+    );
+    assert_eq!(
+      calendar.roll(&date(6, 15), BusinessDayConvention::ModifiedFollowing),
+      date(6, 17)
+    );
+    assert_eq!(
+      calendar.roll(&saturday, BusinessDayConvention::Preceding),
+      date(8, 30)
+    );
+    assert_eq!(
+      calendar.roll(&date(8, 30), BusinessDayConvention::Preceding),
+      date(8, 30)
+    );
+  }
+
+  #[test]
+  #[should_panic]
+  fn test04_no_working_days() {
+    BusinessCalendar::new().with_weekends(vec![
+      DayOfWeek::Sunday,
+      DayOfWeek::Monday,
+      DayOfWeek::Tuesday,
+      DayOfWeek::Wednesday,
+      DayOfWeek::Thursday,
+      DayOfWeek::Friday,
+      DayOfWeek::Saturday,
+    ]);
+  }
+}
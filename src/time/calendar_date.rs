@@ -1,8 +1,8 @@
 use chrono::{Date, Datelike, DateTime, TimeZone, Utc};
 use num::FromPrimitive;
-use time::Duration as OldDuration;
+use chrono::Duration as OldDuration;
 
-use crate::time::{CalendarYearMonth, DayOfMonth, DayOfWeek, TimePoint};
+use crate::time::{CalendarYearMonth, DayOfMonth, DayOfWeek, TimePoint, TimeSource};
 use std::ops::{Sub, Add};
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Hash)]
@@ -73,6 +73,15 @@ impl CalendarDate {
     Self { year_month, day }
   }
 
+  /// 時計 `source` の現在の時点の、タイムゾーン `time_zone` での日付を返す。
+  pub fn today<S, T>(source: &S, time_zone: T) -> Self
+  where
+    S: TimeSource + ?Sized,
+    T: TimeZone,
+  {
+    source.now().into_calendar_date(time_zone)
+  }
+
   pub fn as_year_month(&self) -> &CalendarYearMonth {
     &self.year_month
   }
@@ -112,7 +121,7 @@ impl CalendarDate {
       .to_date_time_on_midnight(time_zone)
       .date()
       .weekday()
-      .number_from_monday();
+      .number_from_sunday();
     DayOfWeek::from_u32(no).unwrap()
   }
 
@@ -134,22 +143,43 @@ impl CalendarDate {
     Self::from(new_date_time)
   }
 
+  /// `months` ヶ月前の日付を返す。
+  ///
+  /// 該当する日が存在しない場合は、その月の最終日に丸める（例: 3月31日の1ヶ月前は2月28日または29日）。
   pub fn subtract_months<T>(&self, months: i64, time_zone: T) -> Self
   where
     T: TimeZone,
   {
-    let date_time = self.to_date_time_on_midnight(time_zone);
-    let new_date_time = date_time - OldDuration::days(30 * months);
-    Self::from(new_date_time)
+    self.add_months(-months, time_zone)
   }
 
-  pub fn add_months<T>(&self, months: i64, time_zone: T) -> Self
+  /// `months` ヶ月後の日付を返す。
+  ///
+  /// 該当する日が存在しない場合は、その月の最終日に丸める（例: 1月31日の1ヶ月後は2月28日または29日）。
+  pub fn add_months<T>(&self, months: i64, _time_zone: T) -> Self
   where
     T: TimeZone,
   {
-    let date_time = self.to_date_time_on_midnight(time_zone);
-    let new_date_time = date_time + OldDuration::days(30 * months);
-    Self::from(new_date_time)
+    let year_month = self.year_month.add_months(months);
+    let last_day = year_month.as_last_day_of_month().clone();
+    let day = if self.day.is_after(&last_day) {
+      last_day
+    } else {
+      self.day.clone()
+    };
+    Self::new(year_month, day)
+  }
+
+  pub fn is_last_day_of_month(&self) -> bool {
+    self.day == *self.year_month.as_last_day_of_month()
+  }
+
+  /// この日付が属する月の最終日を返す。
+  pub fn last_day_of_month(&self) -> Self {
+    Self::new(
+      self.year_month.clone(),
+      self.year_month.as_last_day_of_month().clone(),
+    )
   }
 
   pub fn is_after(&self, other: &Self) -> bool {
@@ -166,3 +196,27 @@ impl CalendarDate {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use chrono::FixedOffset;
+
+  use crate::time::{CalendarDate, DayOfWeek};
+
+  #[test]
+  fn test01_day_of_week() {
+    // 2000-01-01 は土曜日、2024-10-20 は日曜日
+    assert_eq!(
+      CalendarDate::from((2000, 1, 1)).day_of_week_at_utc(),
+      DayOfWeek::Saturday
+    );
+    assert_eq!(
+      CalendarDate::from((2024, 10, 20)).day_of_week_at_utc(),
+      DayOfWeek::Sunday
+    );
+    assert_eq!(
+      CalendarDate::from((2024, 10, 21)).day_of_week(FixedOffset::east_opt(9 * 3600).unwrap()),
+      DayOfWeek::Monday
+    );
+  }
+}
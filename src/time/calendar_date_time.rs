@@ -31,6 +31,14 @@ impl CalendarDateTime {
     Self { date, time }
   }
 
+  pub fn as_date(&self) -> &CalendarDate {
+    &self.date
+  }
+
+  pub fn as_time(&self) -> &TimeOfDay {
+    &self.time
+  }
+
   pub fn is_after(&self, other: &Self) -> bool {
     !self.is_before(other) && self != other
   }
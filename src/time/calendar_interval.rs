@@ -0,0 +1,219 @@
+use chrono::{TimeZone, Utc};
+
+use crate::time::{CalendarDate, CalendarYearMonth, TimeInterval, TimePoint};
+
+/// 開始日と終了日を両端に含む日付の区間。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct CalendarInterval {
+  start: CalendarDate,
+  end: CalendarDate,
+}
+
+impl From<(CalendarDate, CalendarDate)> for CalendarInterval {
+  fn from((start, end): (CalendarDate, CalendarDate)) -> Self {
+    Self::inclusive(start, end)
+  }
+}
+
+impl CalendarInterval {
+  /// `start` から `end` までの区間を返す。`start` が `end` より後の場合はパニックする。
+  pub fn inclusive(start: CalendarDate, end: CalendarDate) -> Self {
+    if start.is_after(&end) {
+      panic!("{:?} is not before or equal to {:?}", start, end)
+    }
+    Self { start, end }
+  }
+
+  /// 年 `year` の1月1日から12月31日までの区間を返す。
+  pub fn year(year: i32) -> Self {
+    Self::inclusive(
+      CalendarDate::from((year, 1, 1)),
+      CalendarDate::from((year, 12, 31)),
+    )
+  }
+
+  /// 年月 `year_month` の初日から最終日までの区間を返す。
+  pub fn month(year_month: CalendarYearMonth) -> Self {
+    let start = CalendarDate::from((year_month.to_year(), year_month.to_month_u32(), 1));
+    let end = start.last_day_of_month();
+    Self::inclusive(start, end)
+  }
+
+  pub fn as_start(&self) -> &CalendarDate {
+    &self.start
+  }
+
+  pub fn as_end(&self) -> &CalendarDate {
+    &self.end
+  }
+
+  pub fn includes(&self, date: &CalendarDate) -> bool {
+    !date.is_before(&self.start) && !date.is_after(&self.end)
+  }
+
+  pub fn intersects(&self, other: &Self) -> bool {
+    !self.end.is_before(&other.start) && !other.end.is_before(&self.start)
+  }
+
+  /// 両端を含む日数を返す。
+  pub fn length_in_days(&self) -> i64 {
+    let start = self.start.to_date_time_on_midnight_at_utc();
+    let end = self.end.to_date_time_on_midnight_at_utc();
+    (end - start).num_days() + 1
+  }
+
+  /// 区間に収まる満了月数を返す（例: 1月15日から2月14日までは1ヶ月、1月1日から12月31日までは12ヶ月）。
+  pub fn length_in_months(&self) -> i64 {
+    let after_end = self.end.add_days(1, Utc);
+    let start_index = Self::month_index(self.start.as_year_month());
+    let end_index = Self::month_index(after_end.as_year_month());
+    let months = end_index - start_index;
+    if self.start.add_months(months, Utc).is_after(&after_end) {
+      months - 1
+    } else {
+      months
+    }
+  }
+
+  /// 区間に含まれる日付を順に返す。
+  pub fn days(&self) -> impl Iterator<Item = CalendarDate> + '_ {
+    (0i64..)
+      .map(move |n| self.start.add_days(n, Utc))
+      .take_while(move |e| !e.is_after(&self.end))
+  }
+
+  /// 開始日から7日ごとに区切った区間を返す。最後の区間は終了日で切り詰める。
+  pub fn weeks(&self) -> impl Iterator<Item = CalendarInterval> + '_ {
+    self.chunks(|start| start.add_days(7, Utc))
+  }
+
+  /// 暦月ごとに区切った区間を返す。最初と最後の区間は区間の両端で切り詰める。
+  pub fn months(&self) -> impl Iterator<Item = CalendarInterval> + '_ {
+    self.chunks(|start| start.last_day_of_month().add_days(1, Utc))
+  }
+
+  /// 暦年ごとに区切った区間を返す。最初と最後の区間は区間の両端で切り詰める。
+  pub fn years(&self) -> impl Iterator<Item = CalendarInterval> + '_ {
+    self.chunks(|start| CalendarDate::from((start.as_year_month().to_year() + 1, 1, 1)))
+  }
+
+  /// タイムゾーン `time_zone` での開始日の0時から終了日の翌日の0時までの半開区間を返す。
+  pub fn to_time_interval<T: TimeZone>(&self, time_zone: T) -> TimeInterval {
+    TimeInterval::right_open(
+      TimePoint::at_midnight_cd_tz(self.start.clone(), time_zone.clone()),
+      TimePoint::at_midnight_cd_tz(self.end.add_days(1, Utc), time_zone),
+    )
+  }
+
+  fn chunks<F>(&self, next_start: F) -> impl Iterator<Item = CalendarInterval> + '_
+  where
+    F: Fn(&CalendarDate) -> CalendarDate + 'static,
+  {
+    let mut start = Some(self.start.clone());
+    std::iter::from_fn(move || {
+      let current = start.take()?;
+      let next = next_start(&current);
+      let end = next.subtract_days(1, Utc);
+      if end.is_before(&self.end) {
+        start = Some(next);
+        Some(Self::inclusive(current, end))
+      } else {
+        Some(Self::inclusive(current, self.end.clone()))
+      }
+    })
+  }
+
+  fn month_index(year_month: &CalendarYearMonth) -> i64 {
+    year_month.to_year() as i64 * 12 + year_month.to_month_u32() as i64
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::FixedOffset;
+
+  use crate::time::{CalendarDate, CalendarInterval, CalendarYearMonth, TimePoint};
+
+  fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+    CalendarDate::from((year, month, day))
+  }
+
+  #[test]
+  fn test01_constructors_and_lengths() {
+    let leap_year = CalendarInterval::year(2024);
+    assert_eq!(leap_year.length_in_days(), 366);
+    assert_eq!(leap_year.length_in_months(), 12);
+
+    let february = CalendarInterval::month(CalendarYearMonth::from((2023, 2)));
+    assert_eq!(february.as_end(), &date(2023, 2, 28));
+    assert_eq!(february.length_in_days(), 28);
+    assert_eq!(february.length_in_months(), 1);
+
+    let contract = CalendarInterval::inclusive(date(2024, 1, 15), date(2024, 2, 14));
+    assert_eq!(contract.length_in_months(), 1);
+    let short = CalendarInterval::inclusive(date(2024, 1, 15), date(2024, 2, 13));
+    assert_eq!(short.length_in_months(), 0);
+    let end_of_month = CalendarInterval::inclusive(date(2024, 1, 31), date(2024, 2, 28));
+    assert_eq!(end_of_month.length_in_months(), 1);
+
+    assert!(contract.includes(&date(2024, 2, 14)));
+    assert!(!contract.includes(&date(2024, 2, 15)));
+    assert!(!contract.intersects(&february));
+    assert!(contract.intersects(&CalendarInterval::month(CalendarYearMonth::from((2024, 2)))));
+  }
+
+  #[test]
+  #[should_panic]
+  fn test02_reversed() {
+    CalendarInterval::inclusive(date(2024, 1, 2), date(2024, 1, 1));
+  }
+
+  #[test]
+  fn test03_iterators() {
+    let interval = CalendarInterval::inclusive(date(2023, 12, 30), date(2024, 2, 10));
+    let days = interval.days().collect::<Vec<_>>();
+    assert_eq!(days.len(), 43);
+    assert_eq!(days[2], date(2024, 1, 1));
+    assert_eq!(days.last(), Some(&date(2024, 2, 10)));
+
+    let weeks = interval.weeks().collect::<Vec<_>>();
+    assert_eq!(weeks.len(), 7);
+    assert_eq!(
+      weeks[6],
+      CalendarInterval::inclusive(date(2024, 2, 10), date(2024, 2, 10))
+    );
+
+    let months = interval.months().collect::<Vec<_>>();
+    assert_eq!(
+      months,
+      vec![
+        CalendarInterval::inclusive(date(2023, 12, 30), date(2023, 12, 31)),
+        CalendarInterval::month(CalendarYearMonth::from((2024, 1))),
+        CalendarInterval::inclusive(date(2024, 2, 1), date(2024, 2, 10)),
+      ]
+    );
+
+    let years = interval.years().collect::<Vec<_>>();
+    assert_eq!(years.len(), 2);
+    assert_eq!(years[1].as_start(), &date(2024, 1, 1));
+  }
+
+  #[test]
+  fn test04_to_time_interval() {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let interval = CalendarInterval::inclusive(date(2024, 1, 1), date(2024, 1, 2));
+    let time_interval = interval.to_time_interval(jst);
+    assert_eq!(
+      time_interval.as_start(),
+      Some(&TimePoint::at_ymd_hms_milli_utc(2023, 12, 31, 15, 0, 0, 0))
+    );
+    assert_eq!(
+      time_interval.as_end(),
+      Some(&TimePoint::at_ymd_hms_milli_utc(2024, 1, 2, 15, 0, 0, 0))
+    );
+    assert_eq!(
+      time_interval.length().unwrap().in_base_units(),
+      2 * 24 * 3600 * 1000
+    );
+  }
+}
@@ -69,7 +69,13 @@ impl CalendarYearMonth {
   }
 
   pub fn as_last_day_of_month(&self) -> &DayOfMonth {
-    self.month.as_last_day()
+    self.month.as_last_day_in(self.year)
+  }
+
+  /// `months` ヶ月後（負の場合は前）の年月を返す。
+  pub fn add_months(&self, months: i64) -> Self {
+    let total = self.year as i64 * 12 + self.to_month_u32() as i64 - 1 + months;
+    Self::from((total.div_euclid(12) as i32, total.rem_euclid(12) as u32 + 1))
   }
 
   pub fn add_month(&mut self) -> &Self {
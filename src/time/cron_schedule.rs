@@ -0,0 +1,585 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::offset::LocalResult;
+use chrono::{Datelike, Duration as OldDuration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+use crate::intervals::{Interval, LimitValue};
+use crate::time::TimePoint;
+
+#[derive(Debug, PartialEq)]
+pub enum CronError {
+  /// フィールドの数が5または6でない。
+  FieldCountError,
+  /// フィールドの書式が正しくない。
+  ParseError,
+  /// 値が範囲外。
+  InvalidValueError,
+  /// 区間に下限がない。
+  UnboundedIntervalError,
+}
+
+/// 日フィールドの要素。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum DayOfMonthItem {
+  Day(u32),
+  /// `L`: 月末日。
+  Last,
+  /// `LW`: 月末の平日。
+  LastWeekday,
+  /// `15W`: 指定日に最も近い同じ月の平日。
+  NearestWeekday(u32),
+}
+
+/// 曜日フィールドの要素。曜日は日曜日を0とする。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+enum DayOfWeekItem {
+  Day(u32),
+  /// `5L`: 月の最終のその曜日。
+  Last(u32),
+  /// `1#2`: 月の第nのその曜日。
+  Nth(u32, u32),
+}
+
+const MONTH_NAMES: [&str; 12] = [
+  "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+const DAY_OF_WEEK_NAMES: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// 次の実行時点を探す年数の上限。これを超えて一致しない式は実行されないものとみなす。
+const SEARCH_YEARS: i32 = 28;
+
+/// 夏時間の切り替えで現地時刻がずれうる最大の時間。
+const MAX_OFFSET_SHIFT_HOURS: i64 = 3;
+
+/// cron 式による実行スケジュール。
+///
+/// 5フィールド（分 時 日 月 曜日）と、秒を先頭に加えた6フィールドの式を解釈する。
+/// 範囲 (`1-5`)・間隔 (`*/15`)・列挙 (`1,15`)・月末 (`L`)・平日 (`W`)・第n曜日 (`#`) を扱う。
+/// 日と曜日の両方を指定した場合は、いずれかに一致する日に実行する。
+///
+/// 夏時間の切り替えで存在しない現地時刻はギャップが終わる時点で1回だけ、重複する現地時刻は最初の時点でのみ実行する。
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CronSchedule {
+  expression: String,
+  seconds: BTreeSet<u32>,
+  minutes: BTreeSet<u32>,
+  hours: BTreeSet<u32>,
+  days_of_month: Option<Vec<DayOfMonthItem>>,
+  months: BTreeSet<u32>,
+  days_of_week: Option<Vec<DayOfWeekItem>>,
+}
+
+impl fmt::Display for CronSchedule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.expression)
+  }
+}
+
+impl FromStr for CronSchedule {
+  type Err = CronError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let fields = s.split_whitespace().collect::<Vec<_>>();
+    let (seconds, rest) = match fields.len() {
+      5 => ("0", &fields[..]),
+      6 => (fields[0], &fields[1..]),
+      _ => return Err(CronError::FieldCountError),
+    };
+    Ok(Self {
+      expression: fields.join(" "),
+      seconds: parse_field(seconds, 0, 59, &[])?,
+      minutes: parse_field(rest[0], 0, 59, &[])?,
+      hours: parse_field(rest[1], 0, 23, &[])?,
+      days_of_month: parse_days_of_month(rest[2])?,
+      months: parse_field(rest[3], 1, 12, &MONTH_NAMES)?,
+      days_of_week: parse_days_of_week(rest[4])?,
+    })
+  }
+}
+
+fn parse_value(value: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, CronError> {
+  let upper = value.to_ascii_uppercase();
+  let parsed = match names.iter().position(|e| *e == upper) {
+    Some(index) => index as u32 + min,
+    None => value.parse::<u32>().map_err(|_| CronError::ParseError)?,
+  };
+  if parsed < min || parsed > max {
+    return Err(CronError::InvalidValueError);
+  }
+  Ok(parsed)
+}
+
+fn parse_field(expr: &str, min: u32, max: u32, names: &[&str]) -> Result<BTreeSet<u32>, CronError> {
+  let mut values = BTreeSet::new();
+  for item in expr.split(',') {
+    let mut parts = item.splitn(2, '/');
+    let range = parts.next().unwrap_or_default();
+    let step = match parts.next() {
+      Some(step) => match step.parse::<u32>() {
+        Ok(0) => return Err(CronError::InvalidValueError),
+        Ok(step) => Some(step),
+        Err(_) => return Err(CronError::ParseError),
+      },
+      None => None,
+    };
+    let (from, to) = if range == "*" || range == "?" {
+      (min, max)
+    } else if let Some((from, to)) = range.split_once('-') {
+      (
+        parse_value(from, min, max, names)?,
+        parse_value(to, min, max, names)?,
+      )
+    } else {
+      let from = parse_value(range, min, max, names)?;
+      (from, if step.is_some() { max } else { from })
+    };
+    if from > to {
+      return Err(CronError::InvalidValueError);
+    }
+    values.extend((from..=to).step_by(step.unwrap_or(1) as usize));
+  }
+  Ok(values)
+}
+
+fn parse_days_of_month(expr: &str) -> Result<Option<Vec<DayOfMonthItem>>, CronError> {
+  if expr == "*" || expr == "?" {
+    return Ok(None);
+  }
+  let mut items = vec![];
+  for item in expr.split(',') {
+    let upper = item.to_ascii_uppercase();
+    match upper.as_str() {
+      "L" => items.push(DayOfMonthItem::Last),
+      "LW" => items.push(DayOfMonthItem::LastWeekday),
+      _ => match upper.strip_suffix('W') {
+        Some(day) => items.push(DayOfMonthItem::NearestWeekday(parse_value(
+          day,
+          1,
+          31,
+          &[],
+        )?)),
+        None => items.extend(
+          parse_field(item, 1, 31, &[])?
+            .into_iter()
+            .map(DayOfMonthItem::Day),
+        ),
+      },
+    }
+  }
+  Ok(Some(items))
+}
+
+fn parse_days_of_week(expr: &str) -> Result<Option<Vec<DayOfWeekItem>>, CronError> {
+  if expr == "*" || expr == "?" {
+    return Ok(None);
+  }
+  let day_of_week = |value: &str| parse_value(value, 0, 7, &DAY_OF_WEEK_NAMES).map(|e| e % 7);
+  let mut items = vec![];
+  for item in expr.split(',') {
+    let upper = item.to_ascii_uppercase();
+    if let Some((day, nth)) = upper.split_once('#') {
+      let nth = nth.parse::<u32>().map_err(|_| CronError::ParseError)?;
+      if !(1..=5).contains(&nth) {
+        return Err(CronError::InvalidValueError);
+      }
+      items.push(DayOfWeekItem::Nth(day_of_week(day)?, nth));
+    } else if let Some(day) = upper.strip_suffix('L').filter(|e| !e.is_empty()) {
+      items.push(DayOfWeekItem::Last(day_of_week(day)?));
+    } else {
+      items.extend(
+        parse_field(item, 0, 7, &DAY_OF_WEEK_NAMES)?
+          .into_iter()
+          .map(|e| DayOfWeekItem::Day(e % 7)),
+      );
+    }
+  }
+  Ok(Some(items))
+}
+
+impl CronSchedule {
+  /// タイムゾーン `time_zone` で、時点 `time_point` より後の最初の実行時点を返す。
+  pub fn next_after<T: TimeZone>(&self, time_point: &TimePoint, time_zone: T) -> Option<TimePoint> {
+    let local = time_point.to_date_time(time_zone.clone()).naive_local();
+    let earliest = local - OldDuration::hours(MAX_OFFSET_SHIFT_HOURS);
+    let start = earliest.date();
+    let end = NaiveDate::from_ymd_opt(start.year() + SEARCH_YEARS, start.month(), 1)?;
+    let mut month = NaiveDate::from_ymd_opt(start.year(), start.month(), 1)?;
+    while month < end {
+      if self.months.contains(&month.month()) {
+        for day in self.days_in(month.year(), month.month()) {
+          let date = NaiveDate::from_ymd_opt(month.year(), month.month(), day)?;
+          if date < start {
+            continue;
+          }
+          for naive in self.times_on(date) {
+            if naive < earliest {
+              continue;
+            }
+            let candidate = Self::resolve(&naive, &time_zone);
+            if candidate.is_after(time_point) {
+              return Some(candidate);
+            }
+          }
+        }
+      }
+      month = Self::add_month(month)?;
+    }
+    None
+  }
+
+  /// タイムゾーン `time_zone` で、時点 `time_point` より前の最後の実行時点を返す。
+  pub fn previous_before<T: TimeZone>(
+    &self,
+    time_point: &TimePoint,
+    time_zone: T,
+  ) -> Option<TimePoint> {
+    let local = time_point.to_date_time(time_zone.clone()).naive_local();
+    let latest = local + OldDuration::hours(MAX_OFFSET_SHIFT_HOURS);
+    let start = latest.date();
+    let end = NaiveDate::from_ymd_opt(start.year() - SEARCH_YEARS, start.month(), 1)?;
+    let mut month = NaiveDate::from_ymd_opt(start.year(), start.month(), 1)?;
+    while month >= end {
+      if self.months.contains(&month.month()) {
+        for day in self.days_in(month.year(), month.month()).into_iter().rev() {
+          let date = NaiveDate::from_ymd_opt(month.year(), month.month(), day)?;
+          if date > start {
+            continue;
+          }
+          for naive in self.times_on(date).into_iter().rev() {
+            if naive > latest {
+              continue;
+            }
+            let candidate = Self::resolve(&naive, &time_zone);
+            if candidate.is_before(time_point) {
+              return Some(candidate);
+            }
+          }
+        }
+      }
+      month = month.pred_opt()?.with_day(1)?;
+    }
+    None
+  }
+
+  /// 時点 `time_point` より後の実行時点を順に返す。
+  pub fn iter_after<'a, T>(
+    &'a self,
+    time_point: TimePoint,
+    time_zone: T,
+  ) -> impl Iterator<Item = TimePoint> + 'a
+  where
+    T: TimeZone + 'a,
+  {
+    let mut current = time_point;
+    std::iter::from_fn(move || {
+      let next = self.next_after(&current, time_zone.clone())?;
+      current = next.clone();
+      Some(next)
+    })
+  }
+
+  /// 区間 `interval` に含まれる実行時点を順に返す。区間に下限がない場合はエラーを返す。
+  pub fn iter_within<'a, T>(
+    &'a self,
+    interval: &'a Interval<TimePoint>,
+    time_zone: T,
+  ) -> Result<impl Iterator<Item = TimePoint> + 'a, CronError>
+  where
+    T: TimeZone + 'a,
+  {
+    let lower = match interval.lower_limit() {
+      LimitValue::Limit(lower) => lower.clone(),
+      LimitValue::Limitless => return Err(CronError::UnboundedIntervalError),
+    };
+    let start = if interval.includes_lower_limit() {
+      TimePoint::new(lower.milliseconds_from_epoc() - 1)
+    } else {
+      lower
+    };
+    Ok(
+      self
+        .iter_after(start, time_zone)
+        .take_while(move |e| interval.includes(&LimitValue::Limit(e.clone()))),
+    )
+  }
+
+  /// 年月の中で実行する日を昇順に返す。
+  fn days_in(&self, year: i32, month: u32) -> Vec<u32> {
+    let last = Self::last_day_of_month(year, month);
+    (1..=last)
+      .filter(|day| {
+        let date = NaiveDate::from_ymd_opt(year, month, *day).unwrap();
+        match (&self.days_of_month, &self.days_of_week) {
+          (None, None) => true,
+          (Some(dom), None) => Self::matches_day_of_month(dom, &date, last),
+          (None, Some(dow)) => Self::matches_day_of_week(dow, &date, last),
+          (Some(dom), Some(dow)) => {
+            Self::matches_day_of_month(dom, &date, last)
+              || Self::matches_day_of_week(dow, &date, last)
+          }
+        }
+      })
+      .collect()
+  }
+
+  fn times_on(&self, date: NaiveDate) -> Vec<NaiveDateTime> {
+    let mut times = vec![];
+    for hour in &self.hours {
+      for minute in &self.minutes {
+        for second in &self.seconds {
+          times.push(date.and_hms_opt(*hour, *minute, *second).unwrap());
+        }
+      }
+    }
+    times
+  }
+
+  fn matches_day_of_month(items: &[DayOfMonthItem], date: &NaiveDate, last: u32) -> bool {
+    let day = date.day();
+    items.iter().any(|item| match item {
+      DayOfMonthItem::Day(value) => *value == day,
+      DayOfMonthItem::Last => day == last,
+      DayOfMonthItem::LastWeekday => {
+        day == Self::nearest_weekday(date.year(), date.month(), last, last)
+      }
+      DayOfMonthItem::NearestWeekday(value) => {
+        *value <= last && day == Self::nearest_weekday(date.year(), date.month(), *value, last)
+      }
+    })
+  }
+
+  fn matches_day_of_week(items: &[DayOfWeekItem], date: &NaiveDate, last: u32) -> bool {
+    let weekday = date.weekday().num_days_from_sunday();
+    let day = date.day();
+    items.iter().any(|item| match item {
+      DayOfWeekItem::Day(value) => *value == weekday,
+      DayOfWeekItem::Last(value) => *value == weekday && day + 7 > last,
+      DayOfWeekItem::Nth(value, nth) => *value == weekday && (day - 1) / 7 + 1 == *nth,
+    })
+  }
+
+  /// `day` 日に最も近い同じ月の平日を返す。
+  fn nearest_weekday(year: i32, month: u32, day: u32, last: u32) -> u32 {
+    let weekday = NaiveDate::from_ymd_opt(year, month, day)
+      .unwrap()
+      .weekday()
+      .num_days_from_sunday();
+    match weekday {
+      6 if day == 1 => 3,
+      6 => day - 1,
+      0 if day == last => day - 2,
+      0 => day + 1,
+      _ => day,
+    }
+  }
+
+  fn last_day_of_month(year: i32, month: u32) -> u32 {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+    (Self::add_month(first).unwrap() - first).num_days() as u32
+  }
+
+  fn add_month(month: NaiveDate) -> Option<NaiveDate> {
+    if month.month() == 12 {
+      NaiveDate::from_ymd_opt(month.year() + 1, 1, 1)
+    } else {
+      NaiveDate::from_ymd_opt(month.year(), month.month() + 1, 1)
+    }
+  }
+
+  /// 現地時刻を時点に変換する。重複する時刻は早い方、存在しない時刻はギャップが終わる時点とする。
+  fn resolve<T: TimeZone>(naive: &NaiveDateTime, time_zone: &T) -> TimePoint {
+    match time_zone.from_local_datetime(naive) {
+      LocalResult::Single(date_time) | LocalResult::Ambiguous(date_time, _) => {
+        TimePoint::from(date_time)
+      }
+      LocalResult::None => {
+        let mut shifted = naive.with_second(0).unwrap();
+        loop {
+          shifted += OldDuration::minutes(1);
+          if let Some(date_time) = time_zone.from_local_datetime(&shifted).earliest() {
+            return TimePoint::from(date_time);
+          }
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{TimeZone, Utc};
+  use chrono_tz::America::New_York;
+  use chrono_tz::Asia::Tokyo;
+
+  use crate::intervals::{Interval, LimitValue};
+  use crate::time::{CronError, CronSchedule, TimePoint};
+
+  fn utc(month: u32, day: u32, hour: u32, minute: u32) -> TimePoint {
+    TimePoint::at_ymd_hms_milli_utc(2024, month, day, hour, minute, 0, 0)
+  }
+
+  fn new_york(month: u32, day: u32, hour: u32, minute: u32) -> TimePoint {
+    TimePoint::from(
+      New_York
+        .with_ymd_and_hms(2024, month, day, hour, minute, 0)
+        .earliest()
+        .unwrap(),
+    )
+  }
+
+  fn schedule(expr: &str) -> CronSchedule {
+    expr.parse().unwrap()
+  }
+
+  #[test]
+  fn test01_parse() {
+    assert_eq!(
+      schedule("*/15  9-17 * * MON-FRI").to_string(),
+      "*/15 9-17 * * MON-FRI"
+    );
+    assert_eq!(
+      "* * * *".parse::<CronSchedule>(),
+      Err(CronError::FieldCountError)
+    );
+    assert_eq!(
+      "60 * * * *".parse::<CronSchedule>(),
+      Err(CronError::InvalidValueError)
+    );
+    assert_eq!(
+      "0 0 * JUX *".parse::<CronSchedule>(),
+      Err(CronError::ParseError)
+    );
+    assert_eq!(
+      "0 0 * * 1#6".parse::<CronSchedule>(),
+      Err(CronError::InvalidValueError)
+    );
+    assert_eq!(
+      "0 0 5-1 * *".parse::<CronSchedule>(),
+      Err(CronError::InvalidValueError)
+    );
+    assert_eq!(
+      "*/0 * * * *".parse::<CronSchedule>(),
+      Err(CronError::InvalidValueError)
+    );
+  }
+
+  #[test]
+  fn test02_next_and_previous() {
+    let every_quarter = schedule("*/15 9-17 * * MON-FRI");
+    // 2024-01-05 は金曜日
+    assert_eq!(
+      every_quarter.next_after(&utc(1, 5, 17, 45), Utc),
+      Some(utc(1, 8, 9, 0))
+    );
+    assert_eq!(
+      every_quarter.next_after(&utc(1, 5, 10, 7), Utc),
+      Some(utc(1, 5, 10, 15))
+    );
+    assert_eq!(
+      every_quarter.previous_before(&utc(1, 8, 9, 0), Utc),
+      Some(utc(1, 5, 17, 45))
+    );
+
+    let with_seconds = schedule("30 0 12 * * *");
+    assert_eq!(
+      with_seconds.next_after(&utc(1, 1, 12, 0), Utc),
+      Some(TimePoint::at_ymd_hms_milli_utc(2024, 1, 1, 12, 0, 30, 0))
+    );
+
+    let tokyo_nine = schedule("0 9 * * *");
+    assert_eq!(
+      tokyo_nine.next_after(&utc(1, 1, 0, 0), Tokyo),
+      Some(utc(1, 2, 0, 0))
+    );
+  }
+
+  #[test]
+  fn test03_special_characters() {
+    let next = |expr: &str, from: TimePoint| schedule(expr).next_after(&from, Utc).unwrap();
+    assert_eq!(next("0 0 L * *", utc(2, 1, 0, 0)), utc(2, 29, 0, 0));
+    // 2024-06-30 は日曜日
+    assert_eq!(next("0 0 LW * *", utc(6, 1, 0, 0)), utc(6, 28, 0, 0));
+    // 2024-06-01 は土曜日なので月曜日の 6/3
+    assert_eq!(next("0 0 1W * *", utc(5, 31, 0, 0)), utc(6, 3, 0, 0));
+    // 2024-09-15 は日曜日
+    assert_eq!(next("0 0 15W * *", utc(9, 1, 0, 0)), utc(9, 16, 0, 0));
+    assert_eq!(next("0 0 * * 5L", utc(5, 1, 0, 0)), utc(5, 31, 0, 0));
+    assert_eq!(next("0 0 * * 4#4", utc(11, 1, 0, 0)), utc(11, 28, 0, 0));
+    assert_eq!(
+      next("0 0 * 2 *", utc(3, 1, 0, 0))
+        .to_date_time_utc()
+        .to_string(),
+      "2025-02-01 00:00:00 UTC"
+    );
+    // 日と曜日の両方を指定した場合はいずれか
+    assert_eq!(next("0 0 13 * FRI", utc(9, 1, 0, 0)), utc(9, 6, 0, 0));
+    assert_eq!(
+      "0 0 30 2 *"
+        .parse::<CronSchedule>()
+        .unwrap()
+        .next_after(&utc(1, 1, 0, 0), Utc),
+      None
+    );
+  }
+
+  #[test]
+  fn test04_daylight_saving_time() {
+    // 2024-03-10 02:00 に 03:00 へ進む
+    let gap = schedule("30 2 * * *");
+    assert_eq!(
+      gap.next_after(&new_york(3, 9, 12, 0), New_York),
+      Some(new_york(3, 10, 3, 0))
+    );
+    assert_eq!(
+      gap.next_after(&new_york(3, 10, 3, 0), New_York),
+      Some(new_york(3, 11, 2, 30))
+    );
+
+    // 2024-11-03 02:00 に 01:00 へ戻る
+    let overlap = schedule("30 1 * * *");
+    let first = overlap
+      .next_after(&new_york(11, 3, 0, 0), New_York)
+      .unwrap();
+    assert_eq!(first, utc(11, 3, 5, 30));
+    assert_eq!(
+      overlap.next_after(&first, New_York),
+      Some(new_york(11, 4, 1, 30))
+    );
+
+    let hourly = schedule("0 * * * *");
+    let times = hourly
+      .iter_after(new_york(11, 3, 0, 30), New_York)
+      .take(3)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      times,
+      vec![utc(11, 3, 5, 0), utc(11, 3, 7, 0), utc(11, 3, 8, 0)]
+    );
+  }
+
+  #[test]
+  fn test05_iter_within() {
+    let interval = Interval::over(
+      LimitValue::Limit(utc(1, 1, 0, 0)),
+      true,
+      LimitValue::Limit(utc(1, 1, 1, 0)),
+      false,
+    );
+    let times = schedule("*/20 * * * *")
+      .iter_within(&interval, Utc)
+      .unwrap()
+      .collect::<Vec<_>>();
+    assert_eq!(
+      times,
+      vec![utc(1, 1, 0, 0), utc(1, 1, 0, 20), utc(1, 1, 0, 40)]
+    );
+
+    let unbounded = Interval::up_to(LimitValue::Limit(utc(1, 1, 1, 0)));
+    assert_eq!(
+      schedule("*/20 * * * *").iter_within(&unbounded, Utc).err(),
+      Some(CronError::UnboundedIntervalError)
+    );
+  }
+}
@@ -0,0 +1,315 @@
+use num::ToPrimitive;
+
+use crate::time::{
+  CalendarDate, CalendarInterval, CalendarYearMonth, DayOfMonth, DayOfWeek, MonthOfYear,
+};
+
+/// 日付が満たすべき条件。
+pub trait DateSpecification {
+  fn is_satisfied_by(&self, date: &CalendarDate) -> bool;
+
+  /// 年 `year` で最初に条件を満たす日付を返す。
+  fn of_year(&self, year: i32) -> Option<CalendarDate> {
+    let interval = CalendarInterval::year(year);
+    let first = interval.days().find(|e| self.is_satisfied_by(e));
+    first
+  }
+
+  /// 区間 `interval` のうち条件を満たす日付を順に返す。
+  fn iterate_over<'a>(
+    &'a self,
+    interval: &'a CalendarInterval,
+  ) -> Box<dyn Iterator<Item = CalendarDate> + 'a> {
+    Box::new(interval.days().filter(move |e| self.is_satisfied_by(e)))
+  }
+
+  /// 両方の条件を満たす条件を返す。
+  fn and<S>(self, other: S) -> AndDateSpecification<Self, S>
+  where
+    Self: Sized,
+    S: DateSpecification,
+  {
+    AndDateSpecification(self, other)
+  }
+
+  /// いずれかの条件を満たす条件を返す。
+  fn or<S>(self, other: S) -> OrDateSpecification<Self, S>
+  where
+    Self: Sized,
+    S: DateSpecification,
+  {
+    OrDateSpecification(self, other)
+  }
+
+  /// この条件を満たさない条件を返す。
+  fn not(self) -> NotDateSpecification<Self>
+  where
+    Self: Sized,
+  {
+    NotDateSpecification(self)
+  }
+}
+
+/// 毎年の決まった月日（例: 11月3日）。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FixedDateSpecification {
+  month: MonthOfYear,
+  day: DayOfMonth,
+}
+
+impl FixedDateSpecification {
+  pub fn new(month: MonthOfYear, day: DayOfMonth) -> Self {
+    Self { month, day }
+  }
+}
+
+impl DateSpecification for FixedDateSpecification {
+  fn is_satisfied_by(&self, date: &CalendarDate) -> bool {
+    *date.as_year_month().as_month_of_year() == self.month && *date.as_day() == self.day
+  }
+
+  /// 2月29日のように、その年に存在しない日付の場合は `None`。
+  fn of_year(&self, year: i32) -> Option<CalendarDate> {
+    let year_month = self.month.clone().on(year);
+    if self.day.is_appliable(year_month.clone()) {
+      Some(self.day.clone().on(year_month))
+    } else {
+      None
+    }
+  }
+}
+
+/// 月の中で何番目の曜日かを表す。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum WeekdayOccurrence {
+  /// 第n（1から5）。
+  Nth(u32),
+  /// 最終。
+  Last,
+}
+
+/// 第n曜日または最終曜日（例: 11月の第4木曜日）。月を指定しない場合は毎月。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct NthDayOfWeekSpecification {
+  month: Option<MonthOfYear>,
+  day_of_week: DayOfWeek,
+  occurrence: WeekdayOccurrence,
+}
+
+impl NthDayOfWeekSpecification {
+  /// コンストラクタ。`WeekdayOccurrence::Nth` が1から5でない場合はパニックする。
+  pub fn new(month: MonthOfYear, day_of_week: DayOfWeek, occurrence: WeekdayOccurrence) -> Self {
+    Self::of(Some(month), day_of_week, occurrence)
+  }
+
+  /// 毎月の第n曜日または最終曜日。
+  pub fn every_month(day_of_week: DayOfWeek, occurrence: WeekdayOccurrence) -> Self {
+    Self::of(None, day_of_week, occurrence)
+  }
+
+  fn of(month: Option<MonthOfYear>, day_of_week: DayOfWeek, occurrence: WeekdayOccurrence) -> Self {
+    if let WeekdayOccurrence::Nth(n) = occurrence {
+      if !(1..=5).contains(&n) {
+        panic!(
+          "Illegal occurrence: {}, please use a value between 1 and 5",
+          n
+        )
+      }
+    }
+    Self {
+      month,
+      day_of_week,
+      occurrence,
+    }
+  }
+
+  /// 年月 `year_month` で条件を満たす日付を返す。第5曜日がない月は `None`。
+  pub fn date_in(&self, year_month: &CalendarYearMonth) -> Option<CalendarDate> {
+    let target = self.day_of_week.to_u32().unwrap();
+    let last_day = year_month.as_last_day_of_month().to_u32();
+    let day = match self.occurrence {
+      WeekdayOccurrence::Nth(n) => {
+        let first = DayOfMonth::new(1)
+          .on(year_month.clone())
+          .day_of_week_at_utc()
+          .to_u32()
+          .unwrap();
+        1 + (target + 7 - first) % 7 + (n - 1) * 7
+      }
+      WeekdayOccurrence::Last => {
+        let last = year_month
+          .as_last_day_of_month()
+          .clone()
+          .on(year_month.clone())
+          .day_of_week_at_utc()
+          .to_u32()
+          .unwrap();
+        last_day - (last + 7 - target) % 7
+      }
+    };
+    if day <= last_day {
+      Some(DayOfMonth::new(day).on(year_month.clone()))
+    } else {
+      None
+    }
+  }
+}
+
+impl DateSpecification for NthDayOfWeekSpecification {
+  fn is_satisfied_by(&self, date: &CalendarDate) -> bool {
+    let month_matches = match &self.month {
+      Some(month) => date.as_year_month().as_month_of_year() == month,
+      None => true,
+    };
+    month_matches && self.date_in(date.as_year_month()).as_ref() == Some(date)
+  }
+
+  fn of_year(&self, year: i32) -> Option<CalendarDate> {
+    match &self.month {
+      Some(month) => self.date_in(&month.clone().on(year)),
+      None => (1..=12).find_map(|e| self.date_in(&CalendarYearMonth::from((year, e)))),
+    }
+  }
+}
+
+/// 月末日。月を指定しない場合は毎月。
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash)]
+pub struct LastDayOfMonthSpecification {
+  month: Option<MonthOfYear>,
+}
+
+impl LastDayOfMonthSpecification {
+  pub fn new(month: MonthOfYear) -> Self {
+    Self { month: Some(month) }
+  }
+
+  pub fn every_month() -> Self {
+    Self { month: None }
+  }
+}
+
+impl DateSpecification for LastDayOfMonthSpecification {
+  fn is_satisfied_by(&self, date: &CalendarDate) -> bool {
+    let month_matches = match &self.month {
+      Some(month) => date.as_year_month().as_month_of_year() == month,
+      None => true,
+    };
+    month_matches && date.is_last_day_of_month()
+  }
+
+  fn of_year(&self, year: i32) -> Option<CalendarDate> {
+    let month = self.month.clone().unwrap_or(MonthOfYear::JAN);
+    Some(CalendarDate::from((year, month.as_value().to_u32().unwrap(), 1)).last_day_of_month())
+  }
+}
+
+/// 両方の条件を満たす条件。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct AndDateSpecification<A, B>(A, B);
+
+impl<A: DateSpecification, B: DateSpecification> DateSpecification for AndDateSpecification<A, B> {
+  fn is_satisfied_by(&self, date: &CalendarDate) -> bool {
+    self.0.is_satisfied_by(date) && self.1.is_satisfied_by(date)
+  }
+}
+
+/// いずれかの条件を満たす条件。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct OrDateSpecification<A, B>(A, B);
+
+impl<A: DateSpecification, B: DateSpecification> DateSpecification for OrDateSpecification<A, B> {
+  fn is_satisfied_by(&self, date: &CalendarDate) -> bool {
+    self.0.is_satisfied_by(date) || self.1.is_satisfied_by(date)
+  }
+}
+
+/// 条件を満たさない条件。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct NotDateSpecification<A>(A);
+
+impl<A: DateSpecification> DateSpecification for NotDateSpecification<A> {
+  fn is_satisfied_by(&self, date: &CalendarDate) -> bool {
+    !self.0.is_satisfied_by(date)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::time::{
+    CalendarDate, CalendarInterval, CalendarYearMonth, DateSpecification, DayOfMonth, DayOfWeek,
+    FixedDateSpecification, LastDayOfMonthSpecification, MonthOfYear, NthDayOfWeekSpecification,
+    WeekdayOccurrence,
+  };
+
+  fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+    CalendarDate::from((year, month, day))
+  }
+
+  #[test]
+  fn test01_fixed_date() {
+    let culture_day = FixedDateSpecification::new(MonthOfYear::NOV, DayOfMonth::new(3));
+    assert!(culture_day.is_satisfied_by(&date(2024, 11, 3)));
+    assert!(!culture_day.is_satisfied_by(&date(2024, 11, 4)));
+    assert_eq!(culture_day.of_year(2025), Some(date(2025, 11, 3)));
+
+    let leap_day = FixedDateSpecification::new(MonthOfYear::FEB, DayOfMonth::new(29));
+    assert_eq!(leap_day.of_year(2024), Some(date(2024, 2, 29)));
+    assert_eq!(leap_day.of_year(2023), None);
+  }
+
+  #[test]
+  fn test02_nth_day_of_week() {
+    let thanksgiving = NthDayOfWeekSpecification::new(
+      MonthOfYear::NOV,
+      DayOfWeek::Thursday,
+      WeekdayOccurrence::Nth(4),
+    );
+    assert_eq!(thanksgiving.of_year(2024), Some(date(2024, 11, 28)));
+    assert_eq!(thanksgiving.of_year(2025), Some(date(2025, 11, 27)));
+    assert!(thanksgiving.is_satisfied_by(&date(2024, 11, 28)));
+    assert!(!thanksgiving.is_satisfied_by(&date(2024, 11, 21)));
+
+    let memorial_day =
+      NthDayOfWeekSpecification::new(MonthOfYear::MAY, DayOfWeek::Monday, WeekdayOccurrence::Last);
+    assert_eq!(memorial_day.of_year(2024), Some(date(2024, 5, 27)));
+
+    let fifth_friday =
+      NthDayOfWeekSpecification::every_month(DayOfWeek::Friday, WeekdayOccurrence::Nth(5));
+    assert_eq!(
+      fifth_friday.date_in(&CalendarYearMonth::from((2024, 2))),
+      None
+    );
+    assert_eq!(fifth_friday.of_year(2024), Some(date(2024, 3, 29)));
+  }
+
+  #[test]
+  fn test03_last_day_of_month() {
+    let month_end = LastDayOfMonthSpecification::every_month();
+    let interval = CalendarInterval::inclusive(date(2024, 1, 15), date(2024, 4, 15));
+    assert_eq!(
+      month_end.iterate_over(&interval).collect::<Vec<_>>(),
+      vec![date(2024, 1, 31), date(2024, 2, 29), date(2024, 3, 31)]
+    );
+    assert_eq!(
+      LastDayOfMonthSpecification::new(MonthOfYear::FEB).of_year(2023),
+      Some(date(2023, 2, 28))
+    );
+  }
+
+  #[test]
+  fn test04_composites() {
+    // 月末日のうち土日でない日
+    let weekend =
+      NthDayOfWeekSpecification::every_month(DayOfWeek::Saturday, WeekdayOccurrence::Last).or(
+        NthDayOfWeekSpecification::every_month(DayOfWeek::Sunday, WeekdayOccurrence::Last),
+      );
+    let billing_day = LastDayOfMonthSpecification::every_month().and(weekend.not());
+    let interval = CalendarInterval::year(2024);
+    let dates = billing_day.iterate_over(&interval).collect::<Vec<_>>();
+    assert_eq!(dates.len(), 8);
+    assert!(!dates.contains(&date(2024, 3, 31)));
+    assert!(!dates.contains(&date(2024, 8, 31)));
+    assert!(!dates.contains(&date(2024, 11, 30)));
+    assert_eq!(billing_day.of_year(2024), Some(date(2024, 1, 31)));
+  }
+}
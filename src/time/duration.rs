@@ -1,3 +1,7 @@
+use std::convert::TryFrom;
+
+use chrono::{DateTime, Months, TimeZone, Utc};
+
 use crate::time::{TimeUnit, TimePoint};
 use std::cmp::Ordering;
 use std::ops::{Add, Sub};
@@ -79,11 +83,19 @@ impl Duration {
     Self::new(how_many, TimeUnit::YEAR)
   }
 
+  pub fn to_quantity(&self) -> i64 {
+    self.quantity
+  }
+
+  pub fn as_unit(&self) -> &TimeUnit {
+    &self.unit
+  }
+
   pub fn in_base_units(&self) -> i64 {
     self.quantity * self.unit.factor()
   }
 
-  fn is_convertible_to(&self, other: &Self) -> bool {
+  pub fn is_convertible_to(&self, other: &Self) -> bool {
     self.unit.is_convertible_to(&other.unit)
   }
 
@@ -133,12 +145,54 @@ impl Duration {
     )
   }
 
+  /// 時点 `point` にこの期間を加える。
+  ///
+  /// 月単位の期間は UTC の暦で月を進め、該当する日が存在しない場合は月末日に丸める。
+  /// 結果が暦で表せる範囲を超える場合は、その範囲の端の時点に丸める。
   pub fn added_to(&self, point: TimePoint) -> TimePoint {
-    TimePoint::from(self.in_base_units() + point.milliseconds_from_epoc())
+    self.checked_added_to(point).unwrap_or_else(|| {
+      if self.quantity < 0 {
+        TimePoint::from(DateTime::<Utc>::MIN_UTC)
+      } else {
+        TimePoint::from(DateTime::<Utc>::MAX_UTC)
+      }
+    })
   }
 
+  /// 時点 `point` からこの期間を引く。範囲の扱いは [Duration::added_to] と同じ。
   pub fn subtracted_from(&self, point: TimePoint) -> TimePoint {
-    TimePoint::from(-self.in_base_units() + point.milliseconds_from_epoc())
+    self.checked_subtracted_from(point).unwrap_or_else(|| {
+      if self.quantity > 0 {
+        TimePoint::from(DateTime::<Utc>::MIN_UTC)
+      } else {
+        TimePoint::from(DateTime::<Utc>::MAX_UTC)
+      }
+    })
+  }
+
+  /// 時点 `point` にこの期間を加える。結果が暦で表せる範囲を超える場合は `None`。
+  pub fn checked_added_to(&self, point: TimePoint) -> Option<TimePoint> {
+    let base_units = self.quantity.checked_mul(self.unit.factor())?;
+    let date_time = if self.unit.is_convertible_to_milliseconds() {
+      let milliseconds = point.milliseconds_from_epoc().checked_add(base_units)?;
+      Utc.timestamp_millis_opt(milliseconds).single()?
+    } else {
+      let date_time = Utc
+        .timestamp_millis_opt(point.milliseconds_from_epoc())
+        .single()?;
+      let delta = Months::new(u32::try_from(base_units.unsigned_abs()).ok()?);
+      if base_units >= 0 {
+        date_time.checked_add_months(delta)?
+      } else {
+        date_time.checked_sub_months(delta)?
+      }
+    };
+    Some(TimePoint::from(date_time))
+  }
+
+  /// 時点 `point` からこの期間を引く。結果が暦で表せる範囲を超える場合は `None`。
+  pub fn checked_subtracted_from(&self, point: TimePoint) -> Option<TimePoint> {
+    Self::new(self.quantity.checked_neg()?, self.unit.clone()).checked_added_to(point)
   }
 
   pub fn divided_by(&self, divisor: Self) -> Ratio {
@@ -0,0 +1,36 @@
+use crate::time::CalendarDate;
+
+/// 名前つきの休日。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Holiday {
+  pub date: CalendarDate,
+  pub name: String,
+}
+
+impl Holiday {
+  pub fn new(date: CalendarDate, name: &str) -> Self {
+    Self {
+      date,
+      name: name.to_string(),
+    }
+  }
+}
+
+/// 年ごとの休日を提供する。
+pub trait HolidayProvider {
+  /// 年 `year` の休日を日付順に返す。
+  fn holidays_in(&self, year: i32) -> Vec<Holiday>;
+
+  /// `date` が休日であればその名前を返す。
+  fn holiday_name(&self, date: &CalendarDate) -> Option<String> {
+    self
+      .holidays_in(date.as_year_month().to_year())
+      .into_iter()
+      .find(|e| e.date == *date)
+      .map(|e| e.name)
+  }
+
+  fn is_holiday(&self, date: &CalendarDate) -> bool {
+    self.holiday_name(date).is_some()
+  }
+}
@@ -39,6 +39,10 @@ impl HourOfDay {
     Self(value)
   }
 
+  pub fn to_u32(&self) -> u32 {
+    self.0
+  }
+
   pub fn is_after(&self, other: &Self) -> bool {
     !self.is_before(other) && self != other
   }
@@ -0,0 +1,361 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{NaiveDate, Utc};
+
+use crate::time::CalendarDate;
+
+#[derive(Debug, PartialEq)]
+pub enum JapaneseEraError {
+  /// 明治より前の日付。
+  BeforeMeijiError,
+  /// 元号の範囲外、または存在しない日付。
+  InvalidDateError,
+  /// 解釈できない文字列。
+  ParseError,
+}
+
+/// 元号。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum JapaneseEra {
+  Meiji,
+  Taisho,
+  Showa,
+  Heisei,
+  Reiwa,
+}
+
+impl JapaneseEra {
+  const ALL: [JapaneseEra; 5] = [
+    JapaneseEra::Meiji,
+    JapaneseEra::Taisho,
+    JapaneseEra::Showa,
+    JapaneseEra::Heisei,
+    JapaneseEra::Reiwa,
+  ];
+
+  pub fn name(&self) -> &'static str {
+    match self {
+      JapaneseEra::Meiji => "明治",
+      JapaneseEra::Taisho => "大正",
+      JapaneseEra::Showa => "昭和",
+      JapaneseEra::Heisei => "平成",
+      JapaneseEra::Reiwa => "令和",
+    }
+  }
+
+  pub fn abbreviation(&self) -> &'static str {
+    match self {
+      JapaneseEra::Meiji => "M",
+      JapaneseEra::Taisho => "T",
+      JapaneseEra::Showa => "S",
+      JapaneseEra::Heisei => "H",
+      JapaneseEra::Reiwa => "R",
+    }
+  }
+
+  pub fn romanized_name(&self) -> &'static str {
+    match self {
+      JapaneseEra::Meiji => "Meiji",
+      JapaneseEra::Taisho => "Taisho",
+      JapaneseEra::Showa => "Showa",
+      JapaneseEra::Heisei => "Heisei",
+      JapaneseEra::Reiwa => "Reiwa",
+    }
+  }
+
+  /// 元号の初日を返す。明治は改元の詔が出された1868年10月23日（明治元年9月8日）とする。
+  pub fn first_day(&self) -> CalendarDate {
+    CalendarDate::from(self.first_ymd())
+  }
+
+  /// 次の元号の前日を返す。現在の元号の場合は `None`。
+  pub fn last_day(&self) -> Option<CalendarDate> {
+    self
+      .next()
+      .map(|e| CalendarDate::from(e.first_ymd()).subtract_days(1, Utc))
+  }
+
+  /// 日付 `date` が属する元号を返す。明治より前の場合は `None`。
+  pub fn of(date: &CalendarDate) -> Option<Self> {
+    Self::ALL
+      .iter()
+      .rev()
+      .find(|e| !date.is_before(&e.first_day()))
+      .copied()
+  }
+
+  fn next(&self) -> Option<Self> {
+    Self::ALL.iter().find(|e| *e > self).copied()
+  }
+
+  fn first_ymd(&self) -> (i32, u32, u32) {
+    match self {
+      JapaneseEra::Meiji => (1868, 10, 23),
+      JapaneseEra::Taisho => (1912, 7, 30),
+      JapaneseEra::Showa => (1926, 12, 25),
+      JapaneseEra::Heisei => (1989, 1, 8),
+      JapaneseEra::Reiwa => (2019, 5, 1),
+    }
+  }
+
+  fn parse_name(s: &str) -> Option<Self> {
+    Self::ALL.iter().copied().find(|e| {
+      s == e.name()
+        || s.eq_ignore_ascii_case(e.abbreviation())
+        || s.eq_ignore_ascii_case(e.romanized_name())
+    })
+  }
+}
+
+/// 和暦の日付（例: 令和6年10月17日）。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct JapaneseDate {
+  era: JapaneseEra,
+  year: u32,
+  month: u32,
+  day: u32,
+}
+
+impl fmt::Display for JapaneseDate {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.to_kanji_string())
+  }
+}
+
+/// 漢字表記（`令和6年10月17日`、`令和元年5月1日`）、略記（`R6.10.17`）、ローマ字表記（`Reiwa 6.10.17`）を解釈する。
+impl FromStr for JapaneseDate {
+  type Err = JapaneseEraError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    let (era, rest) = if let Some(index) = s.find(|c: char| c.is_ascii_digit() || c == '元') {
+      (s[..index].trim(), &s[index..])
+    } else {
+      return Err(JapaneseEraError::ParseError);
+    };
+    let era = JapaneseEra::parse_name(era).ok_or(JapaneseEraError::ParseError)?;
+    let fields = if rest.ends_with('日') {
+      rest
+        .trim_end_matches('日')
+        .split(['年', '月'])
+        .collect::<Vec<_>>()
+    } else {
+      rest.split('.').collect::<Vec<_>>()
+    };
+    let numbers = fields
+      .iter()
+      .enumerate()
+      .map(|(i, e)| match *e {
+        "元" if i == 0 => Ok(1),
+        _ => e.parse::<u32>().map_err(|_| JapaneseEraError::ParseError),
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+    match numbers.as_slice() {
+      [year, month, day] => Self::new(era, *year, *month, *day),
+      _ => Err(JapaneseEraError::ParseError),
+    }
+  }
+}
+
+impl JapaneseDate {
+  /// コンストラクタ。存在しない日付や元号の範囲外の日付の場合はエラーを返す。
+  pub fn new(era: JapaneseEra, year: u32, month: u32, day: u32) -> Result<Self, JapaneseEraError> {
+    let japanese_date = Self {
+      era,
+      year,
+      month,
+      day,
+    };
+    let date = japanese_date
+      .gregorian_ymd()
+      .ok_or(JapaneseEraError::InvalidDateError)?;
+    if JapaneseEra::of(&CalendarDate::from(date)) == Some(era) {
+      Ok(japanese_date)
+    } else {
+      Err(JapaneseEraError::InvalidDateError)
+    }
+  }
+
+  /// 日付 `date` を和暦に変換する。明治より前の場合はエラーを返す。
+  pub fn from_calendar_date(date: &CalendarDate) -> Result<Self, JapaneseEraError> {
+    let era = JapaneseEra::of(date).ok_or(JapaneseEraError::BeforeMeijiError)?;
+    let (first_year, _, _) = era.first_ymd();
+    Ok(Self {
+      era,
+      year: (date.as_year_month().to_year() - first_year + 1) as u32,
+      month: date.as_year_month().to_month_u32(),
+      day: date.as_day().to_u32(),
+    })
+  }
+
+  pub fn to_calendar_date(&self) -> CalendarDate {
+    CalendarDate::from(self.gregorian_ymd().unwrap())
+  }
+
+  pub fn to_era(&self) -> JapaneseEra {
+    self.era
+  }
+
+  pub fn to_year(&self) -> u32 {
+    self.year
+  }
+
+  pub fn to_month(&self) -> u32 {
+    self.month
+  }
+
+  pub fn to_day(&self) -> u32 {
+    self.day
+  }
+
+  /// 漢字で表す。1年は元年とする（例: `令和元年5月1日`）。
+  pub fn to_kanji_string(&self) -> String {
+    let year = if self.year == 1 {
+      "元".to_string()
+    } else {
+      self.year.to_string()
+    };
+    format!(
+      "{}{}年{}月{}日",
+      self.era.name(),
+      year,
+      self.month,
+      self.day
+    )
+  }
+
+  /// 略記で表す（例: `R6.10.17`）。
+  pub fn to_abbreviated_string(&self) -> String {
+    format!(
+      "{}{}.{}.{}",
+      self.era.abbreviation(),
+      self.year,
+      self.month,
+      self.day
+    )
+  }
+
+  /// ローマ字で表す（例: `Reiwa 6.10.17`）。
+  pub fn to_alphabetic_string(&self) -> String {
+    format!(
+      "{} {}.{}.{}",
+      self.era.romanized_name(),
+      self.year,
+      self.month,
+      self.day
+    )
+  }
+
+  fn gregorian_ymd(&self) -> Option<(i32, u32, u32)> {
+    if self.year == 0 {
+      return None;
+    }
+    let (first_year, _, _) = self.era.first_ymd();
+    let year = i32::try_from(self.year - 1)
+      .ok()
+      .and_then(|e| first_year.checked_add(e))?;
+    NaiveDate::from_ymd_opt(year, self.month, self.day).map(|_| (year, self.month, self.day))
+  }
+}
+
+impl CalendarDate {
+  /// 和暦に変換する。明治より前の場合はエラーを返す。
+  pub fn to_japanese_date(&self) -> Result<JapaneseDate, JapaneseEraError> {
+    JapaneseDate::from_calendar_date(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::time::{CalendarDate, JapaneseDate, JapaneseEra, JapaneseEraError};
+
+  fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+    CalendarDate::from((year, month, day))
+  }
+
+  #[test]
+  fn test01_era_boundaries() {
+    let cases = vec![
+      (date(1912, 7, 29), "明治45年7月29日"),
+      (date(1912, 7, 30), "大正元年7月30日"),
+      (date(1926, 12, 24), "大正15年12月24日"),
+      (date(1926, 12, 25), "昭和元年12月25日"),
+      (date(1989, 1, 7), "昭和64年1月7日"),
+      (date(1989, 1, 8), "平成元年1月8日"),
+      (date(2019, 4, 30), "平成31年4月30日"),
+      (date(2019, 5, 1), "令和元年5月1日"),
+      (date(2024, 10, 17), "令和6年10月17日"),
+    ];
+    for (calendar_date, expected) in cases {
+      let japanese_date = calendar_date.to_japanese_date().unwrap();
+      assert_eq!(japanese_date.to_string(), expected);
+      assert_eq!(japanese_date.to_calendar_date(), calendar_date);
+    }
+    assert_eq!(JapaneseEra::Heisei.last_day(), Some(date(2019, 4, 30)));
+    assert_eq!(JapaneseEra::Reiwa.last_day(), None);
+  }
+
+  #[test]
+  fn test02_before_meiji() {
+    assert_eq!(
+      date(1868, 10, 22).to_japanese_date(),
+      Err(JapaneseEraError::BeforeMeijiError)
+    );
+    assert_eq!(
+      date(1868, 10, 23).to_japanese_date().unwrap().to_string(),
+      "明治元年10月23日"
+    );
+  }
+
+  #[test]
+  fn test03_formats() {
+    let japanese_date = date(2024, 10, 17).to_japanese_date().unwrap();
+    assert_eq!(japanese_date.to_abbreviated_string(), "R6.10.17");
+    assert_eq!(japanese_date.to_alphabetic_string(), "Reiwa 6.10.17");
+
+    for s in &["令和6年10月17日", "R6.10.17", "r6.10.17", "Reiwa 6.10.17"] {
+      assert_eq!(s.parse::<JapaneseDate>(), Ok(japanese_date), "{}", s);
+    }
+    assert_eq!(
+      "令和元年5月1日"
+        .parse::<JapaneseDate>()
+        .unwrap()
+        .to_calendar_date(),
+      date(2019, 5, 1)
+    );
+  }
+
+  #[test]
+  fn test04_invalid() {
+    assert_eq!(
+      "平成32年1月1日".parse::<JapaneseDate>(),
+      Err(JapaneseEraError::InvalidDateError)
+    );
+    assert_eq!(
+      "令和元年4月30日".parse::<JapaneseDate>(),
+      Err(JapaneseEraError::InvalidDateError)
+    );
+    assert_eq!(
+      JapaneseDate::new(JapaneseEra::Reiwa, 5, 2, 29),
+      Err(JapaneseEraError::InvalidDateError)
+    );
+    assert_eq!(
+      "R2147483647.1.1".parse::<JapaneseDate>(),
+      Err(JapaneseEraError::InvalidDateError)
+    );
+    assert_eq!(
+      JapaneseDate::new(JapaneseEra::Reiwa, u32::MAX, 1, 1),
+      Err(JapaneseEraError::InvalidDateError)
+    );
+    assert_eq!(
+      "X6.10.17".parse::<JapaneseDate>(),
+      Err(JapaneseEraError::ParseError)
+    );
+    assert_eq!(
+      "令和6年10月".parse::<JapaneseDate>(),
+      Err(JapaneseEraError::ParseError)
+    );
+  }
+}
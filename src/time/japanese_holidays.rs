@@ -0,0 +1,394 @@
+use chrono::Utc;
+use crate::time::{
+  CalendarDate, CalendarYearMonth, DayOfWeek, Holiday, HolidayProvider, NthDayOfWeekSpecification,
+  WeekdayOccurrence,
+};
+
+/// 日本の国民の祝日・振替休日・国民の休日。
+///
+/// 祝日法が施行された1949年から、春分日・秋分日の計算式が使える2150年までに対応する。範囲外の年は休日を持たない。
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct JapaneseHolidays;
+
+const FIRST_YEAR: i32 = 1949;
+const LAST_YEAR: i32 = 2150;
+
+const SUBSTITUTE_HOLIDAY: &str = "振替休日";
+const CITIZENS_HOLIDAY: &str = "国民の休日";
+
+impl HolidayProvider for JapaneseHolidays {
+  fn holidays_in(&self, year: i32) -> Vec<Holiday> {
+    if !(FIRST_YEAR..=LAST_YEAR).contains(&year) {
+      return vec![];
+    }
+    let mut national = Self::national_holidays(year);
+    national.sort_by_key(|(month, day, _)| (*month, *day));
+    let is_national = |month: u32, day: u32| national.iter().any(|e| (e.0, e.1) == (month, day));
+
+    let mut extra = vec![];
+    for (month, day, _) in national.iter() {
+      if let Some(substitute) = Self::substitute_for(year, *month, *day, &is_national) {
+        extra.push((substitute.0, substitute.1, SUBSTITUTE_HOLIDAY));
+      }
+    }
+    for (month, day) in Self::citizens_holidays(year, &national) {
+      if !extra.iter().any(|e| (e.0, e.1) == (month, day)) {
+        extra.push((month, day, CITIZENS_HOLIDAY));
+      }
+    }
+
+    let mut all = national;
+    all.extend(extra);
+    all.sort_by_key(|(month, day, _)| (*month, *day));
+    all
+      .into_iter()
+      .map(|(month, day, name)| Holiday::new(CalendarDate::from((year, month, day)), name))
+      .collect()
+  }
+}
+
+impl JapaneseHolidays {
+  /// 祝日法で定められた祝日と皇室行事による休日を返す。
+  fn national_holidays(year: i32) -> Vec<(u32, u32, &'static str)> {
+    let mut holidays = vec![(1, 1, "元日")];
+    holidays.push(if year < 2000 {
+      (1, 15, "成人の日")
+    } else {
+      (1, Self::nth_monday(year, 1, 2), "成人の日")
+    });
+    if year >= 1967 {
+      holidays.push((2, 11, "建国記念の日"));
+    }
+    holidays.push((3, Self::vernal_equinox_day(year), "春分の日"));
+    holidays.push(match year {
+      _ if year < 1989 => (4, 29, "天皇誕生日"),
+      _ if year < 2007 => (4, 29, "みどりの日"),
+      _ => (4, 29, "昭和の日"),
+    });
+    holidays.push((5, 3, "憲法記念日"));
+    if year >= 2007 {
+      holidays.push((5, 4, "みどりの日"));
+    }
+    holidays.push((5, 5, "こどもの日"));
+    match year {
+      1996..=2002 => holidays.push((7, 20, "海の日")),
+      2020 => holidays.push((7, 23, "海の日")),
+      2021 => holidays.push((7, 22, "海の日")),
+      _ if year >= 2003 => holidays.push((7, Self::nth_monday(year, 7, 3), "海の日")),
+      _ => {}
+    }
+    match year {
+      2020 => holidays.push((8, 10, "山の日")),
+      2021 => holidays.push((8, 8, "山の日")),
+      _ if year >= 2016 => holidays.push((8, 11, "山の日")),
+      _ => {}
+    }
+    match year {
+      1966..=2002 => holidays.push((9, 15, "敬老の日")),
+      _ if year >= 2003 => holidays.push((9, Self::nth_monday(year, 9, 3), "敬老の日")),
+      _ => {}
+    }
+    holidays.push((9, Self::autumnal_equinox_day(year), "秋分の日"));
+    match year {
+      1966..=1999 => holidays.push((10, 10, "体育の日")),
+      2000..=2019 => holidays.push((10, Self::nth_monday(year, 10, 2), "体育の日")),
+      2020 => holidays.push((7, 24, "スポーツの日")),
+      2021 => holidays.push((7, 23, "スポーツの日")),
+      _ if year >= 2022 => holidays.push((10, Self::nth_monday(year, 10, 2), "スポーツの日")),
+      _ => {}
+    }
+    holidays.push((11, 3, "文化の日"));
+    holidays.push((11, 23, "勤労感謝の日"));
+    match year {
+      1949..=1988 => {}
+      1989..=2018 => holidays.push((12, 23, "天皇誕生日")),
+      2019 => {}
+      _ => holidays.push((2, 23, "天皇誕生日")),
+    }
+    holidays.extend(Self::imperial_events(year));
+    holidays
+  }
+
+  /// 皇室の慶弔行事に伴う休日。
+  fn imperial_events(year: i32) -> Vec<(u32, u32, &'static str)> {
+    match year {
+      1959 => vec![(4, 10, "皇太子明仁親王の結婚の儀")],
+      1989 => vec![(2, 24, "昭和天皇の大喪の礼")],
+      1990 => vec![(11, 12, "即位礼正殿の儀")],
+      1993 => vec![(6, 9, "皇太子徳仁親王の結婚の儀")],
+      2019 => vec![(5, 1, "天皇の即位の日"), (10, 22, "即位礼正殿の儀")],
+      _ => vec![],
+    }
+  }
+
+  /// 日曜日に当たった祝日の振替休日を返す（1973年4月12日施行）。
+  ///
+  /// 2007年以降は祝日でない最初の日、それより前は翌日が祝日でない場合に限り翌日。
+  fn substitute_for<F>(year: i32, month: u32, day: u32, is_national: &F) -> Option<(u32, u32)>
+  where
+    F: Fn(u32, u32) -> bool,
+  {
+    let date = CalendarDate::from((year, month, day));
+    if date.is_before(&CalendarDate::from((1973, 4, 12)))
+      || date.day_of_week_at_utc() != DayOfWeek::Sunday
+    {
+      return None;
+    }
+    let mut next = date.add_days(1, Utc);
+    if year >= 2007 {
+      while Self::matches(&next, year, is_national) {
+        next = next.add_days(1, Utc);
+      }
+    } else if Self::matches(&next, year, is_national) {
+      return None;
+    }
+    if next.as_year_month().to_year() != year {
+      return None;
+    }
+    Some((next.as_year_month().to_month_u32(), next.as_day().to_u32()))
+  }
+
+  /// 前日と翌日が祝日である祝日でない日を返す（1985年12月27日施行）。
+  ///
+  /// 2007年より前は日曜日を除く。振替休日との重複は呼び出し側で除く。
+  fn citizens_holidays(year: i32, national: &[(u32, u32, &'static str)]) -> Vec<(u32, u32)> {
+    if year < 1986 {
+      return vec![];
+    }
+    national
+      .iter()
+      .filter_map(|(month, day, _)| {
+        let between = CalendarDate::from((year, *month, *day)).add_days(1, Utc);
+        let after = between.add_days(1, Utc);
+        let is_national = |date: &CalendarDate| {
+          date.as_year_month().to_year() == year
+            && national
+              .iter()
+              .any(|e| (e.0, e.1) == (date.as_year_month().to_month_u32(), date.as_day().to_u32()))
+        };
+        let is_sunday = between.day_of_week_at_utc() == DayOfWeek::Sunday;
+        if !is_national(&between) && is_national(&after) && (year >= 2007 || !is_sunday) {
+          Some((
+            between.as_year_month().to_month_u32(),
+            between.as_day().to_u32(),
+          ))
+        } else {
+          None
+        }
+      })
+      .collect()
+  }
+
+  fn matches<F>(date: &CalendarDate, year: i32, is_national: &F) -> bool
+  where
+    F: Fn(u32, u32) -> bool,
+  {
+    date.as_year_month().to_year() == year
+      && is_national(date.as_year_month().to_month_u32(), date.as_day().to_u32())
+  }
+
+  /// `month` 月の第 `nth` 月曜日の日を返す。
+  fn nth_monday(year: i32, month: u32, nth: u32) -> u32 {
+    NthDayOfWeekSpecification::every_month(DayOfWeek::Monday, WeekdayOccurrence::Nth(nth))
+      .date_in(&CalendarYearMonth::from((year, month)))
+      .unwrap()
+      .as_day()
+      .to_u32()
+  }
+
+  fn vernal_equinox_day(year: i32) -> u32 {
+    Self::equinox_day(year, [20.8357, 20.8431, 21.8510])
+  }
+
+  fn autumnal_equinox_day(year: i32) -> u32 {
+    Self::equinox_day(year, [23.2588, 23.2488, 24.2488])
+  }
+
+  /// 国立天文台の近似式で春分日・秋分日を計算する。
+  fn equinox_day(year: i32, bases: [f64; 3]) -> u32 {
+    let (base, leap_origin) = match year {
+      _ if year < 1980 => (bases[0], 1983),
+      _ if year < 2100 => (bases[1], 1980),
+      _ => (bases[2], 1980),
+    };
+    let elapsed = (year - 1980) as f64;
+    let leaps = ((year - leap_origin) as f64 / 4.0).floor();
+    (base + 0.242194 * elapsed - leaps).floor() as u32
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::time::{BusinessCalendar, CalendarDate, HolidayProvider, JapaneseHolidays};
+
+  fn dates(year: i32, month_days: &[(u32, u32)]) -> Vec<CalendarDate> {
+    month_days
+      .iter()
+      .map(|(month, day)| CalendarDate::from((year, *month, *day)))
+      .collect()
+  }
+
+  fn holiday_dates(year: i32) -> Vec<CalendarDate> {
+    JapaneseHolidays
+      .holidays_in(year)
+      .into_iter()
+      .map(|e| e.date)
+      .collect()
+  }
+
+  #[test]
+  fn test01_known_years() {
+    let corpus: Vec<(i32, Vec<(u32, u32)>)> = vec![
+      (
+        2019,
+        vec![
+          (1, 1),
+          (1, 14),
+          (2, 11),
+          (3, 21),
+          (4, 29),
+          (4, 30),
+          (5, 1),
+          (5, 2),
+          (5, 3),
+          (5, 4),
+          (5, 5),
+          (5, 6),
+          (7, 15),
+          (8, 11),
+          (8, 12),
+          (9, 16),
+          (9, 23),
+          (10, 14),
+          (10, 22),
+          (11, 3),
+          (11, 4),
+          (11, 23),
+        ],
+      ),
+      (
+        2020,
+        vec![
+          (1, 1),
+          (1, 13),
+          (2, 11),
+          (2, 23),
+          (2, 24),
+          (3, 20),
+          (4, 29),
+          (5, 3),
+          (5, 4),
+          (5, 5),
+          (5, 6),
+          (7, 23),
+          (7, 24),
+          (8, 10),
+          (9, 21),
+          (9, 22),
+          (11, 3),
+          (11, 23),
+        ],
+      ),
+      (
+        2021,
+        vec![
+          (1, 1),
+          (1, 11),
+          (2, 11),
+          (2, 23),
+          (3, 20),
+          (4, 29),
+          (5, 3),
+          (5, 4),
+          (5, 5),
+          (7, 22),
+          (7, 23),
+          (8, 8),
+          (8, 9),
+          (9, 20),
+          (9, 23),
+          (11, 3),
+          (11, 23),
+        ],
+      ),
+      (
+        2024,
+        vec![
+          (1, 1),
+          (1, 8),
+          (2, 11),
+          (2, 12),
+          (2, 23),
+          (3, 20),
+          (4, 29),
+          (5, 3),
+          (5, 4),
+          (5, 5),
+          (5, 6),
+          (7, 15),
+          (8, 11),
+          (8, 12),
+          (9, 16),
+          (9, 22),
+          (9, 23),
+          (10, 14),
+          (11, 3),
+          (11, 4),
+          (11, 23),
+        ],
+      ),
+    ];
+    for (year, expected) in corpus {
+      assert_eq!(holiday_dates(year), dates(year, &expected), "{}", year);
+    }
+  }
+
+  #[test]
+  fn test02_names() {
+    let name = |y, m, d| JapaneseHolidays.holiday_name(&CalendarDate::from((y, m, d)));
+    assert_eq!(name(2019, 4, 30), Some("国民の休日".to_string()));
+    assert_eq!(name(2019, 5, 1), Some("天皇の即位の日".to_string()));
+    assert_eq!(name(2024, 9, 23), Some("振替休日".to_string()));
+    assert_eq!(name(2026, 9, 22), Some("国民の休日".to_string()));
+    assert_eq!(name(2006, 4, 29), Some("みどりの日".to_string()));
+    assert_eq!(name(2007, 4, 29), Some("昭和の日".to_string()));
+    assert_eq!(name(2024, 6, 3), None);
+  }
+
+  #[test]
+  fn test03_historical_rules() {
+    let is_holiday = |y, m, d| JapaneseHolidays.is_holiday(&CalendarDate::from((y, m, d)));
+    // 振替休日の施行前後
+    assert!(!is_holiday(1967, 1, 16));
+    assert!(is_holiday(1973, 4, 30));
+    // 2007年以降は祝日が続く場合に振替休日が後ろにずれる
+    assert!(is_holiday(2008, 5, 6));
+    // 1998年5月4日は国民の休日ではなく振替休日
+    assert_eq!(
+      JapaneseHolidays.holiday_name(&CalendarDate::from((1998, 5, 4))),
+      Some("振替休日".to_string())
+    );
+    assert!(is_holiday(1988, 5, 4));
+    assert!(is_holiday(1989, 2, 24));
+    assert!(is_holiday(2009, 9, 22));
+    assert!(!is_holiday(2019, 12, 23));
+    assert!(JapaneseHolidays.holidays_in(1948).is_empty());
+  }
+
+  #[test]
+  fn test04_business_calendar() {
+    let calendar = BusinessCalendar::new().with_holidays_from(&JapaneseHolidays, 2024..=2025);
+    let before_golden_week = CalendarDate::from((2024, 5, 2));
+    assert_eq!(
+      calendar.next_business_day(&before_golden_week),
+      CalendarDate::from((2024, 5, 7))
+    );
+    assert_eq!(
+      calendar.business_days_between(
+        &CalendarDate::from((2024, 12, 30)),
+        &CalendarDate::from((2025, 1, 7))
+      ),
+      5
+    );
+  }
+}
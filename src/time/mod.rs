@@ -1,28 +1,51 @@
 use num::{FromPrimitive, ToPrimitive};
 
+pub use business_calendar::*;
 pub use calendar_date::*;
 pub use calendar_date_time::*;
+pub use calendar_interval::*;
 pub use calendar_year_month::*;
+pub use cron_schedule::*;
+pub use date_specification::*;
 pub use day_of_month::*;
+pub use duration::*;
+pub use holiday::*;
 pub use hour_of_day::*;
+pub use japanese_era::*;
+pub use japanese_holidays::*;
 pub use minute_of_hour::*;
 pub use month_of_year::*;
+pub use time_interval::*;
 pub use time_point::*;
+pub use time_source::*;
 pub use time_unit::*;
 pub use time_of_day::*;
+pub use zoned_date_time::*;
 
+pub mod recurrence;
+
+mod business_calendar;
 mod calendar_date;
 mod calendar_date_time;
+mod calendar_interval;
 mod calendar_year_month;
+mod cron_schedule;
+mod date_specification;
 mod day_of_month;
 mod duration;
+mod holiday;
 mod hour_of_day;
+mod japanese_era;
+mod japanese_holidays;
 mod minute_of_hour;
 mod month_of_year;
+mod time_interval;
 mod time_of_day;
 mod time_point;
+mod time_source;
 mod time_unit;
 mod time_unit_conversion_factor;
+mod zoned_date_time;
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Hash)]
 pub enum DayOfWeek {
@@ -123,6 +123,15 @@ impl MonthOfYear {
     }
   }
 
+  /// `year` 年におけるこの月の最終日を返す。
+  pub fn as_last_day_in(&self, year: i32) -> &DayOfMonth {
+    if self.value == Month::February && is_leap_year(year) {
+      &MonthOfYear::DAY_OF_MONTH_29
+    } else {
+      &self.last_day
+    }
+  }
+
   pub fn add_with_overflow(&self) -> (Self, bool) {
     let month_num = self.value.to_i64().unwrap() + 1i64;
     if month_num > Month::December.to_i64().unwrap() {
@@ -157,4 +166,12 @@ mod tests {
     assert_eq!(moy.value, Month::January);
     assert_eq!(moy.last_day, DayOfMonth::new(31))
   }
+
+  #[test]
+  fn test_as_last_day_in() {
+    let february = MonthOfYear::from_month(Month::February);
+    assert_eq!(february.as_last_day_in(2023), &DayOfMonth::new(28));
+    assert_eq!(february.as_last_day_in(2024), &DayOfMonth::new(29));
+    assert_eq!(february.as_last_day_in(1900), &DayOfMonth::new(28));
+  }
 }
@@ -0,0 +1,19 @@
+//! iCalendar (RFC 5545) の `RRULE` による繰り返し。
+
+pub use recurrence_rule::*;
+pub use recurrence_set::*;
+
+mod recurrence_rule;
+mod recurrence_set;
+
+#[derive(Debug, PartialEq)]
+pub enum RecurrenceError {
+  /// `RRULE` の書式が正しくない。
+  ParseError,
+  /// `FREQ` が指定されていない。
+  MissingFrequencyError,
+  /// 値が範囲外、または組み合わせが正しくない。
+  InvalidValueError,
+  /// RFC 5545 では有効だが、このモジュールでは扱わない指定（`HOURLY` や `BYHOUR` など）。
+  UnsupportedError,
+}
@@ -0,0 +1,910 @@
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{NaiveDate, NaiveDateTime, TimeZone, Utc};
+use num::{FromPrimitive, ToPrimitive};
+
+use crate::time::recurrence::RecurrenceError;
+use crate::time::{CalendarDate, CalendarYearMonth, DayOfMonth, DayOfWeek, MonthOfYear, TimePoint};
+
+/// 繰り返しの単位。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Frequency {
+  Daily,
+  Weekly,
+  Monthly,
+  Yearly,
+}
+
+impl Frequency {
+  fn code(&self) -> &'static str {
+    match self {
+      Frequency::Daily => "DAILY",
+      Frequency::Weekly => "WEEKLY",
+      Frequency::Monthly => "MONTHLY",
+      Frequency::Yearly => "YEARLY",
+    }
+  }
+
+  /// 間隔の上限。これを超える間隔では2番目の期間が年の上限を超える。
+  fn max_interval(&self) -> u32 {
+    let periods_per_year = match self {
+      Frequency::Daily => 366,
+      Frequency::Weekly => 53,
+      Frequency::Monthly => 12,
+      Frequency::Yearly => 1,
+    };
+    periods_per_year * LAST_YEAR as u32
+  }
+}
+
+/// `BYDAY` の要素（例: `MO`、`2TU`、`-1FR`）。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct WeekdayNum {
+  pub ordinal: Option<i32>,
+  pub day_of_week: DayOfWeek,
+}
+
+impl WeekdayNum {
+  /// 期間中のすべての `day_of_week`。
+  pub fn every(day_of_week: DayOfWeek) -> Self {
+    Self {
+      ordinal: None,
+      day_of_week,
+    }
+  }
+
+  /// 期間中の `ordinal` 番目の `day_of_week`。負の場合は末尾から数える。
+  pub fn nth(ordinal: i32, day_of_week: DayOfWeek) -> Self {
+    Self {
+      ordinal: Some(ordinal),
+      day_of_week,
+    }
+  }
+}
+
+/// 繰り返しの終わり。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Until {
+  Date(CalendarDate),
+  DateTime(TimePoint),
+}
+
+impl Until {
+  fn includes_date(&self, date: &CalendarDate) -> bool {
+    match self {
+      Until::Date(until) => !date.is_after(until),
+      Until::DateTime(until) => !date.is_after(&until.clone().into_calendar_date_utc()),
+    }
+  }
+
+  fn includes_time_point(&self, date: &CalendarDate, time_point: &TimePoint) -> bool {
+    match self {
+      Until::Date(until) => !date.is_after(until),
+      Until::DateTime(until) => !time_point.is_after(until),
+    }
+  }
+}
+
+/// `RRULE` で表される繰り返しの規則。
+///
+/// `FREQ` は `DAILY`・`WEEKLY`・`MONTHLY`・`YEARLY` に対応し、`INTERVAL`・`COUNT`・`UNTIL`・`BYDAY`・`BYMONTHDAY`・`BYMONTH`・`BYSETPOS`・`WKST` を扱う。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RecurrenceRule {
+  frequency: Frequency,
+  interval: u32,
+  count: Option<u32>,
+  until: Option<Until>,
+  by_day: Vec<WeekdayNum>,
+  by_month_day: Vec<i32>,
+  by_month: Vec<MonthOfYear>,
+  by_set_pos: Vec<i32>,
+  week_start: DayOfWeek,
+}
+
+/// 年月日の上限。条件を満たす日付が現れない規則でも展開が終わるようにする。
+const LAST_YEAR: i32 = 9999;
+
+impl RecurrenceRule {
+  /// コンストラクタ。間隔は1、週の始まりは月曜日とする。
+  pub fn new(frequency: Frequency) -> Self {
+    Self {
+      frequency,
+      interval: 1,
+      count: None,
+      until: None,
+      by_day: vec![],
+      by_month_day: vec![],
+      by_month: vec![],
+      by_set_pos: vec![],
+      week_start: DayOfWeek::Monday,
+    }
+  }
+
+  /// 間隔を指定する。ゼロまたは上限を超える場合はパニックする。
+  pub fn with_interval(mut self, interval: u32) -> Self {
+    if interval == 0 || interval > self.frequency.max_interval() {
+      panic!("Illegal value for INTERVAL: {}", interval)
+    }
+    self.interval = interval;
+    self
+  }
+
+  /// 回数を指定する。終了日時と同時に指定した場合はパニックする。
+  pub fn with_count(mut self, count: u32) -> Self {
+    if self.until.is_some() {
+      panic!("COUNT and UNTIL must not occur in the same rule")
+    }
+    self.count = Some(count);
+    self
+  }
+
+  /// 終了日時を指定する。回数と同時に指定した場合はパニックする。
+  pub fn with_until(mut self, until: Until) -> Self {
+    if self.count.is_some() {
+      panic!("COUNT and UNTIL must not occur in the same rule")
+    }
+    self.until = Some(until);
+    self
+  }
+
+  pub fn with_by_day(mut self, by_day: Vec<WeekdayNum>) -> Self {
+    self.by_day = by_day;
+    self
+  }
+
+  /// 日を指定する。負の場合は月末から数える。ゼロまたは31を超える場合はパニックする。
+  pub fn with_by_month_day(mut self, by_month_day: Vec<i32>) -> Self {
+    if by_month_day.iter().any(|e| *e == 0 || e.abs() > 31) {
+      panic!("Illegal value for BYMONTHDAY: {:?}", by_month_day)
+    }
+    self.by_month_day = by_month_day;
+    self
+  }
+
+  pub fn with_by_month(mut self, by_month: Vec<MonthOfYear>) -> Self {
+    self.by_month = by_month;
+    self
+  }
+
+  /// 期間内で何番目の日付を残すかを指定する。負の場合は末尾から数える。ゼロの場合はパニックする。
+  pub fn with_by_set_pos(mut self, by_set_pos: Vec<i32>) -> Self {
+    if by_set_pos.contains(&0) {
+      panic!("Illegal value for BYSETPOS: {:?}", by_set_pos)
+    }
+    self.by_set_pos = by_set_pos;
+    self
+  }
+
+  pub fn with_week_start(mut self, week_start: DayOfWeek) -> Self {
+    self.week_start = week_start;
+    self
+  }
+
+  pub fn to_frequency(&self) -> Frequency {
+    self.frequency
+  }
+
+  pub fn to_interval(&self) -> u32 {
+    self.interval
+  }
+
+  pub fn to_count(&self) -> Option<u32> {
+    self.count
+  }
+
+  pub fn as_until(&self) -> Option<&Until> {
+    self.until.as_ref()
+  }
+
+  /// `start` を起点とする繰り返しの日付を順に返す。
+  ///
+  /// `start` 自体は規則を満たす場合に限り含める。`COUNT` と `UNTIL` を適用する。
+  pub fn dates_from(&self, start: CalendarDate) -> impl Iterator<Item = CalendarDate> + '_ {
+    self
+      .expand(start)
+      .take_while(move |e| self.until.as_ref().is_none_or(|u| u.includes_date(e)))
+      .take(self.count_limit())
+  }
+
+  pub(crate) fn is_within_until(&self, date: &CalendarDate, time_point: &TimePoint) -> bool {
+    self
+      .until
+      .as_ref()
+      .is_none_or(|u| u.includes_time_point(date, time_point))
+  }
+
+  pub(crate) fn count_limit(&self) -> usize {
+    self.count.map_or(usize::MAX, |e| e as usize)
+  }
+
+  /// `COUNT` と `UNTIL` を適用せずに、`start` 以降の日付を順に返す。
+  pub(crate) fn expand(&self, start: CalendarDate) -> impl Iterator<Item = CalendarDate> + '_ {
+    let mut index = 0i64;
+    let mut buffer = VecDeque::new();
+    std::iter::from_fn(move || loop {
+      if let Some(date) = buffer.pop_front() {
+        return Some(date);
+      }
+      let candidates = self.dates_in_period(&start, index)?;
+      index += 1;
+      buffer.extend(candidates.into_iter().filter(|e| !e.is_before(&start)));
+    })
+  }
+
+  /// `start` から数えて `index` 番目の期間の日付を返す。年が上限を超えた場合は `None`。
+  fn dates_in_period(&self, start: &CalendarDate, index: i64) -> Option<Vec<CalendarDate>> {
+    let step = index.checked_mul(self.interval as i64)?;
+    let mut dates = match self.frequency {
+      Frequency::Daily => {
+        Self::check_offset(start, step / 366)?;
+        let day = start.add_days(step, Utc);
+        Self::check_year(day.as_year_month().to_year())?;
+        vec![day]
+          .into_iter()
+          .filter(|e| self.matches_month(e) && self.matches_month_day(e) && self.matches_weekday(e))
+          .collect()
+      }
+      Frequency::Weekly => {
+        let days = step.checked_mul(7)?;
+        Self::check_offset(start, days / 366)?;
+        let offset =
+          (Self::weekday_number(start) + 7 - Self::weekday_number_of(&self.week_start)) % 7;
+        let week_start = start.subtract_days(offset as i64, Utc).add_days(days, Utc);
+        Self::check_year(week_start.as_year_month().to_year())?;
+        let week = (0..7)
+          .map(|e| week_start.add_days(e, Utc))
+          .collect::<Vec<_>>();
+        week
+          .into_iter()
+          .filter(|e| {
+            if self.by_day.is_empty() {
+              Self::weekday_number(e) == Self::weekday_number(start)
+            } else {
+              self.matches_weekday(e)
+            }
+          })
+          .filter(|e| self.matches_month(e))
+          .collect()
+      }
+      Frequency::Monthly => {
+        Self::check_offset(start, step / 12)?;
+        let year_month = start.as_year_month().add_months(step);
+        Self::check_year(year_month.to_year())?;
+        if self.by_month.is_empty() || self.by_month.contains(year_month.as_month_of_year()) {
+          self.dates_in_month(start, &year_month)
+        } else {
+          vec![]
+        }
+      }
+      Frequency::Yearly => {
+        Self::check_offset(start, step)?;
+        let year = start.as_year_month().to_year() + step as i32;
+        self.dates_in_year(start, year)
+      }
+    };
+    dates.sort_by_key(Self::sort_key);
+    dates.dedup();
+    Some(self.select_positions(dates))
+  }
+
+  fn dates_in_month(
+    &self,
+    start: &CalendarDate,
+    year_month: &CalendarYearMonth,
+  ) -> Vec<CalendarDate> {
+    let days = Self::days_of_month(year_month);
+    if !self.by_month_day.is_empty() {
+      let by_day = self.expand_weekdays(&days);
+      days
+        .into_iter()
+        .filter(|e| self.matches_month_day(e))
+        .filter(|e| self.by_day.is_empty() || by_day.contains(e))
+        .collect()
+    } else if !self.by_day.is_empty() {
+      self.expand_weekdays(&days)
+    } else {
+      days
+        .into_iter()
+        .filter(|e| e.as_day() == start.as_day())
+        .collect()
+    }
+  }
+
+  fn dates_in_year(&self, start: &CalendarDate, year: i32) -> Vec<CalendarDate> {
+    if !self.by_month.is_empty() {
+      return self
+        .by_month
+        .iter()
+        .flat_map(|e| self.dates_in_month(start, &e.clone().on(year)))
+        .collect();
+    }
+    let days = (1..=12)
+      .flat_map(|e| Self::days_of_month(&CalendarYearMonth::from((year, e))))
+      .collect::<Vec<_>>();
+    if !self.by_month_day.is_empty() {
+      let by_day = self.expand_weekdays(&days);
+      days
+        .into_iter()
+        .filter(|e| self.matches_month_day(e))
+        .filter(|e| self.by_day.is_empty() || by_day.contains(e))
+        .collect()
+    } else if !self.by_day.is_empty() {
+      self.expand_weekdays(&days)
+    } else {
+      days
+        .into_iter()
+        .filter(|e| {
+          e.as_year_month().as_month_of_year() == start.as_year_month().as_month_of_year()
+        })
+        .filter(|e| e.as_day() == start.as_day())
+        .collect()
+    }
+  }
+
+  /// 期間 `days` から `BYDAY` に該当する日付を返す。
+  fn expand_weekdays(&self, days: &[CalendarDate]) -> Vec<CalendarDate> {
+    let mut result = vec![];
+    for weekday_num in &self.by_day {
+      let target = Self::weekday_number_of(&weekday_num.day_of_week);
+      let matched = days
+        .iter()
+        .filter(|e| Self::weekday_number(e) == target)
+        .collect::<Vec<_>>();
+      match weekday_num.ordinal {
+        None => result.extend(matched.into_iter().cloned()),
+        Some(n) => {
+          if let Some(date) = Self::nth(&matched, n) {
+            result.push((*date).clone());
+          }
+        }
+      }
+    }
+    result
+  }
+
+  fn select_positions(&self, dates: Vec<CalendarDate>) -> Vec<CalendarDate> {
+    if self.by_set_pos.is_empty() {
+      return dates;
+    }
+    let mut selected = self
+      .by_set_pos
+      .iter()
+      .filter_map(|e| Self::nth(&dates, *e).cloned())
+      .collect::<Vec<_>>();
+    selected.sort_by_key(Self::sort_key);
+    selected.dedup();
+    selected
+  }
+
+  fn matches_month(&self, date: &CalendarDate) -> bool {
+    self.by_month.is_empty()
+      || self
+        .by_month
+        .contains(date.as_year_month().as_month_of_year())
+  }
+
+  fn matches_month_day(&self, date: &CalendarDate) -> bool {
+    let day = date.as_day().to_u32() as i32;
+    let last_day = date.as_year_month().as_last_day_of_month().to_u32() as i32;
+    self.by_month_day.is_empty()
+      || self
+        .by_month_day
+        .iter()
+        .any(|e| *e == day || *e == day - last_day - 1)
+  }
+
+  fn matches_weekday(&self, date: &CalendarDate) -> bool {
+    self.by_day.is_empty()
+      || self
+        .by_day
+        .iter()
+        .any(|e| Self::weekday_number_of(&e.day_of_week) == Self::weekday_number(date))
+  }
+
+  fn nth<T>(items: &[T], n: i32) -> Option<&T> {
+    if n > 0 {
+      items.get(n as usize - 1)
+    } else {
+      items
+        .len()
+        .checked_sub(n.unsigned_abs() as usize)
+        .and_then(|e| items.get(e))
+    }
+  }
+
+  fn days_of_month(year_month: &CalendarYearMonth) -> Vec<CalendarDate> {
+    (1..=year_month.as_last_day_of_month().to_u32())
+      .map(|e| DayOfMonth::new(e).on(year_month.clone()))
+      .collect()
+  }
+
+  fn check_year(year: i32) -> Option<()> {
+    if year <= LAST_YEAR {
+      Some(())
+    } else {
+      None
+    }
+  }
+
+  /// `start` の `years` 年後が上限を超える場合は `None`。
+  fn check_offset(start: &CalendarDate, years: i64) -> Option<()> {
+    let years = i32::try_from(years).ok()?;
+    Self::check_year(start.as_year_month().to_year().checked_add(years)?)
+  }
+
+  fn sort_key(date: &CalendarDate) -> (i32, u32, u32) {
+    (
+      date.as_year_month().to_year(),
+      date.as_year_month().to_month_u32(),
+      date.as_day().to_u32(),
+    )
+  }
+
+  fn weekday_number(date: &CalendarDate) -> u32 {
+    Self::weekday_number_of(&date.day_of_week_at_utc())
+  }
+
+  fn weekday_number_of(day_of_week: &DayOfWeek) -> u32 {
+    day_of_week.to_u32().unwrap()
+  }
+
+  fn validate(&self) -> Result<(), RecurrenceError> {
+    let has_ordinal = self.by_day.iter().any(|e| e.ordinal.is_some());
+    let invalid = (has_ordinal
+      && !matches!(self.frequency, Frequency::Monthly | Frequency::Yearly))
+      || (!self.by_month_day.is_empty() && self.frequency == Frequency::Weekly)
+      || (!self.by_set_pos.is_empty()
+        && self.by_day.is_empty()
+        && self.by_month_day.is_empty()
+        && self.by_month.is_empty());
+    if invalid {
+      Err(RecurrenceError::InvalidValueError)
+    } else {
+      Ok(())
+    }
+  }
+}
+
+const WEEKDAY_CODES: [&str; 7] = ["SU", "MO", "TU", "WE", "TH", "FR", "SA"];
+
+fn weekday_code(day_of_week: &DayOfWeek) -> &'static str {
+  WEEKDAY_CODES[day_of_week.to_usize().unwrap() - 1]
+}
+
+fn parse_weekday(code: &str) -> Result<DayOfWeek, RecurrenceError> {
+  WEEKDAY_CODES
+    .iter()
+    .position(|e| *e == code)
+    .and_then(|e| DayOfWeek::from_usize(e + 1))
+    .ok_or(RecurrenceError::ParseError)
+}
+
+fn parse_list<T, F>(value: &str, parse: F) -> Result<Vec<T>, RecurrenceError>
+where
+  F: Fn(&str) -> Result<T, RecurrenceError>,
+{
+  value.split(',').map(|e| parse(e.trim())).collect()
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, RecurrenceError> {
+  value.parse::<T>().map_err(|_| RecurrenceError::ParseError)
+}
+
+fn parse_weekday_num(value: &str) -> Result<WeekdayNum, RecurrenceError> {
+  if value.len() < 2 || !value.is_char_boundary(value.len() - 2) {
+    return Err(RecurrenceError::ParseError);
+  }
+  let (ordinal, code) = value.split_at(value.len() - 2);
+  let day_of_week = parse_weekday(code)?;
+  if ordinal.is_empty() {
+    return Ok(WeekdayNum::every(day_of_week));
+  }
+  let ordinal = parse_number::<i32>(ordinal)?;
+  if ordinal == 0 || ordinal.abs() > 53 {
+    return Err(RecurrenceError::InvalidValueError);
+  }
+  Ok(WeekdayNum::nth(ordinal, day_of_week))
+}
+
+fn parse_until(value: &str) -> Result<Until, RecurrenceError> {
+  if let Ok(date) = NaiveDate::parse_from_str(value, "%Y%m%d") {
+    return Ok(Until::Date(CalendarDate::from(
+      Utc.from_utc_datetime(&date.and_hms_opt(0, 0, 0).unwrap()),
+    )));
+  }
+  if let Some(local) = value.strip_suffix('Z') {
+    let date_time = NaiveDateTime::parse_from_str(local, "%Y%m%dT%H%M%S")
+      .map_err(|_| RecurrenceError::ParseError)?;
+    return Ok(Until::DateTime(TimePoint::from(
+      Utc.from_utc_datetime(&date_time),
+    )));
+  }
+  if NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").is_ok() {
+    // タイムゾーンを持たない日時は DTSTART のタイムゾーンに依存するため扱わない
+    return Err(RecurrenceError::UnsupportedError);
+  }
+  Err(RecurrenceError::ParseError)
+}
+
+/// `FREQ=MONTHLY;BYDAY=MO,TU;BYSETPOS=-1` の形式を解釈する。先頭の `RRULE:` は省略できる。
+impl FromStr for RecurrenceRule {
+  type Err = RecurrenceError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    let s = s.strip_prefix("RRULE:").unwrap_or(s);
+    let parts = s
+      .split(';')
+      .filter(|e| !e.is_empty())
+      .map(|e| {
+        let mut kv = e.splitn(2, '=');
+        match (kv.next(), kv.next()) {
+          (Some(key), Some(value)) => Ok((key.trim().to_ascii_uppercase(), value.trim())),
+          _ => Err(RecurrenceError::ParseError),
+        }
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    let frequency = match parts.iter().find(|(key, _)| key == "FREQ") {
+      Some((_, "DAILY")) => Frequency::Daily,
+      Some((_, "WEEKLY")) => Frequency::Weekly,
+      Some((_, "MONTHLY")) => Frequency::Monthly,
+      Some((_, "YEARLY")) => Frequency::Yearly,
+      Some((_, "SECONDLY")) | Some((_, "MINUTELY")) | Some((_, "HOURLY")) => {
+        return Err(RecurrenceError::UnsupportedError)
+      }
+      Some(_) => return Err(RecurrenceError::ParseError),
+      None => return Err(RecurrenceError::MissingFrequencyError),
+    };
+
+    let mut rule = RecurrenceRule::new(frequency);
+    for (key, value) in parts {
+      match key.as_str() {
+        "FREQ" => {}
+        "INTERVAL" => {
+          rule.interval = parse_number(value)?;
+          if rule.interval == 0 || rule.interval > frequency.max_interval() {
+            return Err(RecurrenceError::InvalidValueError);
+          }
+        }
+        "COUNT" => rule.count = Some(parse_number(value)?),
+        "UNTIL" => rule.until = Some(parse_until(value)?),
+        "BYDAY" => rule.by_day = parse_list(value, parse_weekday_num)?,
+        "BYMONTHDAY" => {
+          rule.by_month_day = parse_list(value, parse_number::<i32>)?;
+          if rule.by_month_day.iter().any(|e| *e == 0 || e.abs() > 31) {
+            return Err(RecurrenceError::InvalidValueError);
+          }
+        }
+        "BYMONTH" => {
+          rule.by_month = parse_list(value, |e| {
+            let month = parse_number::<u32>(e)?;
+            FromPrimitive::from_u32(month)
+              .map(MonthOfYear::from_month)
+              .ok_or(RecurrenceError::InvalidValueError)
+          })?
+        }
+        "BYSETPOS" => {
+          rule.by_set_pos = parse_list(value, parse_number::<i32>)?;
+          if rule.by_set_pos.iter().any(|e| *e == 0 || e.abs() > 366) {
+            return Err(RecurrenceError::InvalidValueError);
+          }
+        }
+        "WKST" => rule.week_start = parse_weekday(value)?,
+        "BYSECOND" | "BYMINUTE" | "BYHOUR" | "BYYEARDAY" | "BYWEEKNO" => {
+          return Err(RecurrenceError::UnsupportedError)
+        }
+        _ => return Err(RecurrenceError::ParseError),
+      }
+    }
+    if rule.count.is_some() && rule.until.is_some() {
+      return Err(RecurrenceError::InvalidValueError);
+    }
+    rule.validate()?;
+    Ok(rule)
+  }
+}
+
+impl fmt::Display for RecurrenceRule {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let join = |items: Vec<String>| items.join(",");
+    let mut parts = vec![format!("FREQ={}", self.frequency.code())];
+    if self.interval != 1 {
+      parts.push(format!("INTERVAL={}", self.interval));
+    }
+    if let Some(count) = self.count {
+      parts.push(format!("COUNT={}", count));
+    }
+    match &self.until {
+      Some(Until::Date(date)) => parts.push(format!(
+        "UNTIL={:04}{:02}{:02}",
+        date.as_year_month().to_year(),
+        date.as_year_month().to_month_u32(),
+        date.as_day().to_u32()
+      )),
+      Some(Until::DateTime(time_point)) => parts.push(format!(
+        "UNTIL={}",
+        time_point.to_date_time_utc().format("%Y%m%dT%H%M%SZ")
+      )),
+      None => {}
+    }
+    if !self.by_month.is_empty() {
+      parts.push(format!(
+        "BYMONTH={}",
+        join(
+          self
+            .by_month
+            .iter()
+            .map(|e| e.as_value().to_u32().unwrap().to_string())
+            .collect()
+        )
+      ));
+    }
+    if !self.by_month_day.is_empty() {
+      parts.push(format!(
+        "BYMONTHDAY={}",
+        join(self.by_month_day.iter().map(|e| e.to_string()).collect())
+      ));
+    }
+    if !self.by_day.is_empty() {
+      parts.push(format!(
+        "BYDAY={}",
+        join(
+          self
+            .by_day
+            .iter()
+            .map(|e| match e.ordinal {
+              Some(n) => format!("{}{}", n, weekday_code(&e.day_of_week)),
+              None => weekday_code(&e.day_of_week).to_string(),
+            })
+            .collect()
+        )
+      ));
+    }
+    if !self.by_set_pos.is_empty() {
+      parts.push(format!(
+        "BYSETPOS={}",
+        join(self.by_set_pos.iter().map(|e| e.to_string()).collect())
+      ));
+    }
+    if self.week_start != DayOfWeek::Monday {
+      parts.push(format!("WKST={}", weekday_code(&self.week_start)));
+    }
+    write!(f, "{}", parts.join(";"))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::time::recurrence::{Frequency, RecurrenceError, RecurrenceRule, Until, WeekdayNum};
+  use crate::time::{CalendarDate, DayOfWeek, MonthOfYear};
+
+  fn date(year: i32, month: u32, day: u32) -> CalendarDate {
+    CalendarDate::from((year, month, day))
+  }
+
+  fn expand(rule: &str, start: CalendarDate, limit: usize) -> Vec<CalendarDate> {
+    let rule = rule.parse::<RecurrenceRule>().unwrap();
+    rule.dates_from(start).take(limit).collect()
+  }
+
+  #[test]
+  fn test01_parse_and_display() {
+    let rule = "RRULE:FREQ=MONTHLY;INTERVAL=2;COUNT=10;BYDAY=1MO,-1FR;WKST=SU"
+      .parse::<RecurrenceRule>()
+      .unwrap();
+    assert_eq!(
+      rule,
+      RecurrenceRule::new(Frequency::Monthly)
+        .with_interval(2)
+        .with_count(10)
+        .with_by_day(vec![
+          WeekdayNum::nth(1, DayOfWeek::Monday),
+          WeekdayNum::nth(-1, DayOfWeek::Friday),
+        ])
+        .with_week_start(DayOfWeek::Sunday)
+    );
+    assert_eq!(
+      rule.to_string(),
+      "FREQ=MONTHLY;INTERVAL=2;COUNT=10;BYDAY=1MO,-1FR;WKST=SU"
+    );
+
+    let until = "FREQ=YEARLY;UNTIL=20301231T235959Z;BYMONTH=11"
+      .parse::<RecurrenceRule>()
+      .unwrap();
+    assert!(matches!(until.as_until(), Some(Until::DateTime(_))));
+    assert_eq!(
+      until.to_string(),
+      "FREQ=YEARLY;UNTIL=20301231T235959Z;BYMONTH=11"
+    );
+  }
+
+  #[test]
+  fn test02_parse_errors() {
+    let error = |s: &str| s.parse::<RecurrenceRule>().unwrap_err();
+    assert_eq!(error("INTERVAL=2"), RecurrenceError::MissingFrequencyError);
+    assert_eq!(error("FREQ=FORTNIGHTLY"), RecurrenceError::ParseError);
+    assert_eq!(error("FREQ=HOURLY"), RecurrenceError::UnsupportedError);
+    assert_eq!(
+      error("FREQ=DAILY;BYHOUR=9"),
+      RecurrenceError::UnsupportedError
+    );
+    assert_eq!(
+      error("FREQ=DAILY;COUNT=3;UNTIL=20240101"),
+      RecurrenceError::InvalidValueError
+    );
+    assert_eq!(
+      error("FREQ=WEEKLY;BYDAY=2MO"),
+      RecurrenceError::InvalidValueError
+    );
+    assert_eq!(
+      error("FREQ=MONTHLY;BYMONTHDAY=32"),
+      RecurrenceError::InvalidValueError
+    );
+    assert_eq!(
+      error("FREQ=MONTHLY;BYMONTH=13"),
+      RecurrenceError::InvalidValueError
+    );
+    assert_eq!(
+      error("FREQ=MONTHLY;BYSETPOS=1"),
+      RecurrenceError::InvalidValueError
+    );
+    assert_eq!(error("FREQ=MONTHLY;BYDAY=XX"), RecurrenceError::ParseError);
+    assert_eq!(
+      error("FREQ=DAILY;INTERVAL=0"),
+      RecurrenceError::InvalidValueError
+    );
+    assert_eq!(
+      error("FREQ=DAILY;INTERVAL=4294967295"),
+      RecurrenceError::InvalidValueError
+    );
+    assert_eq!(
+      error("FREQ=YEARLY;INTERVAL=10000"),
+      RecurrenceError::InvalidValueError
+    );
+  }
+
+  #[test]
+  fn test03_daily_and_weekly() {
+    assert_eq!(
+      expand("FREQ=DAILY;INTERVAL=10;COUNT=3", date(2024, 1, 25), 10),
+      vec![date(2024, 1, 25), date(2024, 2, 4), date(2024, 2, 14)]
+    );
+    // 隔週の火曜日と木曜日
+    assert_eq!(
+      expand(
+        "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;UNTIL=20240215",
+        date(2024, 1, 2),
+        10
+      ),
+      vec![
+        date(2024, 1, 2),
+        date(2024, 1, 4),
+        date(2024, 1, 16),
+        date(2024, 1, 18),
+        date(2024, 1, 30),
+        date(2024, 2, 1),
+        date(2024, 2, 13),
+        date(2024, 2, 15),
+      ]
+    );
+    // RFC 5545 の WKST の例
+    assert_eq!(
+      expand(
+        "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=MO",
+        date(1997, 8, 5),
+        10
+      ),
+      vec![
+        date(1997, 8, 5),
+        date(1997, 8, 10),
+        date(1997, 8, 19),
+        date(1997, 8, 24)
+      ]
+    );
+    assert_eq!(
+      expand(
+        "FREQ=WEEKLY;INTERVAL=2;COUNT=4;BYDAY=TU,SU;WKST=SU",
+        date(1997, 8, 5),
+        10
+      ),
+      vec![
+        date(1997, 8, 5),
+        date(1997, 8, 17),
+        date(1997, 8, 19),
+        date(1997, 8, 31)
+      ]
+    );
+  }
+
+  #[test]
+  fn test04_monthly() {
+    // 月の最終営業日
+    assert_eq!(
+      expand(
+        "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1",
+        date(2024, 1, 1),
+        4
+      ),
+      vec![
+        date(2024, 1, 31),
+        date(2024, 2, 29),
+        date(2024, 3, 29),
+        date(2024, 4, 30)
+      ]
+    );
+    // 31日がない月は飛ばす
+    assert_eq!(
+      expand("FREQ=MONTHLY;COUNT=3", date(2024, 1, 31), 10),
+      vec![date(2024, 1, 31), date(2024, 3, 31), date(2024, 5, 31)]
+    );
+    assert_eq!(
+      expand("FREQ=MONTHLY;BYMONTHDAY=-1", date(2024, 1, 15), 3),
+      vec![date(2024, 1, 31), date(2024, 2, 29), date(2024, 3, 31)]
+    );
+    // 13日の金曜日
+    assert_eq!(
+      expand("FREQ=MONTHLY;BYDAY=FR;BYMONTHDAY=13", date(2024, 1, 1), 3),
+      vec![date(2024, 9, 13), date(2024, 12, 13), date(2025, 6, 13)]
+    );
+  }
+
+  #[test]
+  fn test05_yearly() {
+    // 11月の第4木曜日
+    assert_eq!(
+      expand("FREQ=YEARLY;BYMONTH=11;BYDAY=4TH", date(2024, 1, 1), 2),
+      vec![date(2024, 11, 28), date(2025, 11, 27)]
+    );
+    assert_eq!(
+      expand("FREQ=YEARLY;COUNT=2", date(2024, 2, 29), 10),
+      vec![date(2024, 2, 29), date(2028, 2, 29)]
+    );
+    // 年の20番目の月曜日
+    assert_eq!(
+      expand("FREQ=YEARLY;BYDAY=20MO", date(1997, 1, 1), 3),
+      vec![date(1997, 5, 19), date(1998, 5, 18), date(1999, 5, 17)]
+    );
+    let rule = RecurrenceRule::new(Frequency::Yearly)
+      .with_by_month(vec![MonthOfYear::JAN, MonthOfYear::JUL])
+      .with_by_month_day(vec![1])
+      .with_until(Until::Date(date(2025, 1, 1)));
+    assert_eq!(
+      rule.dates_from(date(2024, 1, 1)).collect::<Vec<_>>(),
+      vec![date(2024, 1, 1), date(2024, 7, 1), date(2025, 1, 1)]
+    );
+  }
+
+  #[test]
+  fn test06_never_matching_rule_terminates() {
+    let rule = "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=30"
+      .parse::<RecurrenceRule>()
+      .unwrap();
+    assert_eq!(rule.dates_from(date(2024, 1, 1)).next(), None);
+  }
+
+  #[test]
+  fn test07_large_interval_terminates() {
+    for frequency in [
+      Frequency::Daily,
+      Frequency::Weekly,
+      Frequency::Monthly,
+      Frequency::Yearly,
+    ] {
+      let mut rule = RecurrenceRule::new(frequency);
+      rule.interval = u32::MAX;
+      assert_eq!(
+        rule.dates_from(date(2024, 1, 1)).collect::<Vec<_>>(),
+        vec![date(2024, 1, 1)]
+      );
+    }
+    let rule = RecurrenceRule::new(Frequency::Yearly).with_interval(9999);
+    assert_eq!(
+      rule.dates_from(date(2024, 1, 1)).collect::<Vec<_>>(),
+      vec![date(2024, 1, 1)]
+    );
+  }
+}
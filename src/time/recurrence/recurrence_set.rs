@@ -0,0 +1,171 @@
+use std::collections::HashSet;
+
+use chrono::TimeZone;
+
+use crate::time::recurrence::RecurrenceRule;
+use crate::time::zoned_date_time::resolve_local;
+use crate::time::{
+  CalendarDate, CalendarDateTime, GapPolicy, OverlapPolicy, TimeOfDay, TimePoint, ZonedDateTime,
+};
+
+/// 開始日 (`DTSTART`) と規則 (`RRULE`) と除外日 (`EXDATE`) からなる繰り返し。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurrenceSet {
+  start: CalendarDate,
+  rule: RecurrenceRule,
+  exdates: HashSet<CalendarDate>,
+}
+
+impl RecurrenceSet {
+  pub fn new(start: CalendarDate, rule: RecurrenceRule) -> Self {
+    Self {
+      start,
+      rule,
+      exdates: HashSet::new(),
+    }
+  }
+
+  /// 除外日を追加する。
+  pub fn with_exdate(mut self, date: CalendarDate) -> Self {
+    self.exdates.insert(date);
+    self
+  }
+
+  /// 除外日をまとめて追加する。
+  pub fn with_exdates<I>(mut self, dates: I) -> Self
+  where
+    I: IntoIterator<Item = CalendarDate>,
+  {
+    self.exdates.extend(dates);
+    self
+  }
+
+  pub fn as_start(&self) -> &CalendarDate {
+    &self.start
+  }
+
+  pub fn as_rule(&self) -> &RecurrenceRule {
+    &self.rule
+  }
+
+  /// 繰り返しの日付を順に返す。
+  ///
+  /// 除外日は `COUNT` を数えた後に取り除くため、除外日があっても回数は増えない。
+  pub fn dates(&self) -> impl Iterator<Item = CalendarDate> + '_ {
+    self
+      .rule
+      .dates_from(self.start.clone())
+      .filter(move |e| !self.exdates.contains(e))
+  }
+
+  /// タイムゾーン `time_zone` での時刻 `time_of_day` の時点を順に返す。
+  ///
+  /// 日時で指定された `UNTIL` は時点で比較する。RFC 5545 に従い、夏時間の切り替えで存在しない時刻は
+  /// 切り替え前のオフセットで解釈し（例: 2:30 は 3:30 になる）、2回現れる時刻は早い方とする。
+  pub fn time_points<'a, T>(
+    &'a self,
+    time_of_day: TimeOfDay,
+    time_zone: T,
+  ) -> impl Iterator<Item = TimePoint> + 'a
+  where
+    T: TimeZone + 'a,
+  {
+    self
+      .rule
+      .expand(self.start.clone())
+      .map(move |e| {
+        let naive =
+          ZonedDateTime::to_naive_date_time(&CalendarDateTime::new(e.clone(), time_of_day.clone()));
+        let time_point = resolve_local(
+          &naive,
+          &time_zone,
+          GapPolicy::ShiftForward,
+          OverlapPolicy::Earlier,
+        )
+        .unwrap_or_else(|err| panic!("{:?}: {:?}", naive, err));
+        (e, time_point)
+      })
+      .take_while(move |(date, time_point)| self.rule.is_within_until(date, time_point))
+      .take(self.rule.count_limit())
+      .filter(move |(date, _)| !self.exdates.contains(date))
+      .map(|(_, time_point)| time_point)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::FixedOffset;
+  use chrono_tz::America::New_York;
+
+  use crate::time::recurrence::{RecurrenceRule, RecurrenceSet};
+  use crate::time::{CalendarDate, TimeOfDay, TimePoint};
+
+  fn date(month: u32, day: u32) -> CalendarDate {
+    CalendarDate::from((2024, month, day))
+  }
+
+  #[test]
+  fn test01_exdates() {
+    let rule = "FREQ=WEEKLY;BYDAY=MO;COUNT=4"
+      .parse::<RecurrenceRule>()
+      .unwrap();
+    let set = RecurrenceSet::new(date(1, 1), rule).with_exdate(date(1, 15));
+    assert_eq!(
+      set.dates().collect::<Vec<_>>(),
+      vec![date(1, 1), date(1, 8), date(1, 22)]
+    );
+  }
+
+  #[test]
+  fn test02_time_points() {
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    let rule = "FREQ=DAILY;UNTIL=20240103T000000Z"
+      .parse::<RecurrenceRule>()
+      .unwrap();
+    let set = RecurrenceSet::new(date(1, 1), rule);
+
+    // 日本時間 8:00 は UTC の前日 23:00 なので 1月3日分も含まれる
+    let morning = set
+      .time_points(TimeOfDay::from((8, 0)), jst)
+      .collect::<Vec<_>>();
+    assert_eq!(
+      morning,
+      vec![
+        TimePoint::at_ymd_hms_milli_utc(2023, 12, 31, 23, 0, 0, 0),
+        TimePoint::at_ymd_hms_milli_utc(2024, 1, 1, 23, 0, 0, 0),
+        TimePoint::at_ymd_hms_milli_utc(2024, 1, 2, 23, 0, 0, 0),
+      ]
+    );
+    let evening = set
+      .time_points(TimeOfDay::from((18, 0)), jst)
+      .collect::<Vec<_>>();
+    assert_eq!(evening.len(), 2);
+  }
+
+  #[test]
+  fn test03_daylight_saving_time() {
+    // 2024-03-10 2:00 に 3:00 へ進むため、その日の 2:30 は存在しない
+    let rule = "FREQ=DAILY;COUNT=3".parse::<RecurrenceRule>().unwrap();
+    let set = RecurrenceSet::new(date(3, 9), rule);
+    assert_eq!(
+      set
+        .time_points(TimeOfDay::from((2, 30)), New_York)
+        .collect::<Vec<_>>(),
+      vec![
+        TimePoint::at_ymd_hms_milli_utc(2024, 3, 9, 7, 30, 0, 0),
+        TimePoint::at_ymd_hms_milli_utc(2024, 3, 10, 7, 30, 0, 0),
+        TimePoint::at_ymd_hms_milli_utc(2024, 3, 11, 6, 30, 0, 0),
+      ]
+    );
+
+    // 2024-11-03 の 1:30 は2回現れるので早い方（夏時間）
+    let rule = "FREQ=DAILY;COUNT=1".parse::<RecurrenceRule>().unwrap();
+    let set = RecurrenceSet::new(date(11, 3), rule);
+    assert_eq!(
+      set
+        .time_points(TimeOfDay::from((1, 30)), New_York)
+        .collect::<Vec<_>>(),
+      vec![TimePoint::at_ymd_hms_milli_utc(2024, 11, 3, 5, 30, 0, 0)]
+    );
+  }
+}
@@ -0,0 +1,381 @@
+use core::fmt;
+
+use chrono::{TimeZone, Utc};
+
+use crate::intervals::{Interval, LimitValue};
+use crate::time::{Duration, TimePoint};
+
+#[derive(Debug, PartialEq)]
+pub enum TimeIntervalError {
+  /// 区間に下限または上限がない。
+  UnboundedError,
+  /// 期間の長さがゼロ以下。
+  NonPositiveLengthError,
+}
+
+/// 時点の区間。
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeInterval(Interval<TimePoint>);
+
+impl From<Interval<TimePoint>> for TimeInterval {
+  fn from(interval: Interval<TimePoint>) -> Self {
+    Self(interval)
+  }
+}
+
+/// UTC で表示する。
+impl fmt::Display for TimeInterval {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.to_string_in(Utc))
+  }
+}
+
+impl TimeInterval {
+  pub fn over(
+    start: LimitValue<TimePoint>,
+    start_included: bool,
+    end: LimitValue<TimePoint>,
+    end_included: bool,
+  ) -> Self {
+    Self(Interval::over(start, start_included, end, end_included))
+  }
+
+  /// `start` 以上 `end` 以下の区間を返す。
+  pub fn closed(start: TimePoint, end: TimePoint) -> Self {
+    Self(Interval::closed(
+      LimitValue::Limit(start),
+      LimitValue::Limit(end),
+    ))
+  }
+
+  /// `start` 以上 `end` 未満の区間を返す。
+  pub fn right_open(start: TimePoint, end: TimePoint) -> Self {
+    Self::over(
+      LimitValue::Limit(start),
+      true,
+      LimitValue::Limit(end),
+      false,
+    )
+  }
+
+  /// `start` から期間 `length` だけ続く区間 `[start, start + length)` を返す。
+  ///
+  /// `length` がゼロ以下の場合はエラーを返す。
+  pub fn starting_from(start: TimePoint, length: Duration) -> Result<Self, TimeIntervalError> {
+    Self::check_length(&length)?;
+    let end = length.added_to(start.clone());
+    Ok(Self::right_open(start, end))
+  }
+
+  /// `end` までの期間 `length` の区間 `[end - length, end)` を返す。
+  ///
+  /// `length` がゼロ以下の場合はエラーを返す。
+  pub fn preceding(end: TimePoint, length: Duration) -> Result<Self, TimeIntervalError> {
+    Self::check_length(&length)?;
+    let start = length.subtracted_from(end.clone());
+    Ok(Self::right_open(start, end))
+  }
+
+  fn check_length(length: &Duration) -> Result<(), TimeIntervalError> {
+    if length.to_quantity() <= 0 {
+      Err(TimeIntervalError::NonPositiveLengthError)
+    } else {
+      Ok(())
+    }
+  }
+
+  /// `start` 以降の上限のない区間を返す。
+  pub fn everafter(start: TimePoint) -> Self {
+    Self(Interval::and_more(LimitValue::Limit(start)))
+  }
+
+  pub fn as_interval(&self) -> &Interval<TimePoint> {
+    &self.0
+  }
+
+  /// 開始時点を返す。下限がない場合は `None`。
+  pub fn as_start(&self) -> Option<&TimePoint> {
+    match self.0.lower_limit() {
+      LimitValue::Limit(tp) => Some(tp),
+      LimitValue::Limitless => None,
+    }
+  }
+
+  /// 終了時点を返す。上限がない場合は `None`。
+  pub fn as_end(&self) -> Option<&TimePoint> {
+    match self.0.upper_limit() {
+      LimitValue::Limit(tp) => Some(tp),
+      LimitValue::Limitless => None,
+    }
+  }
+
+  pub fn includes_start(&self) -> bool {
+    self.0.includes_lower_limit()
+  }
+
+  pub fn includes_end(&self) -> bool {
+    self.0.includes_upper_limit()
+  }
+
+  /// 開始から終了までの長さをミリ秒単位で返す。上限または下限がない場合はエラーを返す。
+  pub fn length(&self) -> Result<Duration, TimeIntervalError> {
+    match (self.as_start(), self.as_end()) {
+      (Some(start), Some(end)) => Ok(Duration::milliseconds(
+        end.milliseconds_from_epoc() - start.milliseconds_from_epoc(),
+      )),
+      _ => Err(TimeIntervalError::UnboundedError),
+    }
+  }
+
+  /// 時点 `time_point` が区間に含まれるかを返す。
+  pub fn is_during(&self, time_point: &TimePoint) -> bool {
+    self.0.includes(&LimitValue::Limit(time_point.clone()))
+  }
+
+  /// 区間全体が時点 `time_point` より後にあるかを返す。
+  pub fn is_after(&self, time_point: &TimePoint) -> bool {
+    self.0.is_above(&LimitValue::Limit(time_point.clone()))
+  }
+
+  /// 区間全体が時点 `time_point` より前にあるかを返す。
+  pub fn is_before(&self, time_point: &TimePoint) -> bool {
+    self.0.is_below(&LimitValue::Limit(time_point.clone()))
+  }
+
+  pub fn intersects(&self, other: &Self) -> bool {
+    self.0.intersects(&other.0)
+  }
+
+  /// 開始時点から1日ずつ進めた、区間に含まれる時点を返す。下限がない場合はエラーを返す。
+  pub fn days_iterator(&self) -> Result<impl Iterator<Item = TimePoint> + '_, TimeIntervalError> {
+    if self.as_start().is_none() {
+      return Err(TimeIntervalError::UnboundedError);
+    }
+    Ok(
+      self
+        .steps_by(Duration::days(1))
+        .take_while(move |e| !self.is_before(e))
+        .filter(move |e| self.is_during(e)),
+    )
+  }
+
+  /// 開始時点から期間 `length` ごとに区切った半開区間を返す。
+  ///
+  /// 区間の終了を超える端数の区間は含めない。下限がない場合、または `length` がゼロ以下の場合はエラーを返す。
+  pub fn subintervals_by(
+    &self,
+    length: Duration,
+  ) -> Result<impl Iterator<Item = TimeInterval> + '_, TimeIntervalError> {
+    if self.as_start().is_none() {
+      return Err(TimeIntervalError::UnboundedError);
+    }
+    Self::check_length(&length)?;
+    let starts = self.steps_by(length.clone());
+    let ends = self.steps_by(length).skip(1);
+    Ok(
+      starts
+        .zip(ends)
+        .take_while(move |(_, end)| match self.as_end() {
+          Some(limit) => end <= limit,
+          None => true,
+        })
+        .map(|(start, end)| Self::right_open(start, end)),
+    )
+  }
+
+  /// 開始時点から `step` の整数倍だけ進めた時点を返す。月単位の期間でも端数が蓄積しない。
+  ///
+  /// 暦で表せる範囲を超えたところで終わる。
+  fn steps_by(&self, step: Duration) -> impl Iterator<Item = TimePoint> {
+    let start = self
+      .as_start()
+      .unwrap_or_else(|| panic!("{:?} has no lower limit", self.0))
+      .clone();
+    (0i64..).map_while(move |n| {
+      let quantity = step.to_quantity().checked_mul(n)?;
+      Duration::new(quantity, step.as_unit().clone()).checked_added_to(start.clone())
+    })
+  }
+
+  /// タイムゾーン `time_zone` で区間を文字列にする。例: `[2024-01-01 00:00:00 +09:00, 2024-01-02 00:00:00 +09:00)`
+  pub fn to_string_in<T>(&self, time_zone: T) -> String
+  where
+    T: TimeZone,
+    T::Offset: fmt::Display,
+  {
+    let format = |limit: Option<&TimePoint>, limitless: &str| match limit {
+      Some(tp) => tp
+        .to_date_time(time_zone.clone())
+        .format("%Y-%m-%d %H:%M:%S %:z")
+        .to_string(),
+      None => limitless.to_string(),
+    };
+    format!(
+      "{}{}, {}{}",
+      if self.as_start().is_some() && self.includes_start() {
+        "["
+      } else {
+        "("
+      },
+      format(self.as_start(), "-∞"),
+      format(self.as_end(), "+∞"),
+      if self.as_end().is_some() && self.includes_end() {
+        "]"
+      } else {
+        ")"
+      },
+    )
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{DateTime, FixedOffset, Utc};
+
+  use crate::intervals::LimitValue;
+  use crate::time::{Duration, TimeInterval, TimeIntervalError, TimePoint};
+
+  fn at(month: u32, day: u32, hour: u32) -> TimePoint {
+    TimePoint::at_ymd_hms_milli_utc(2024, month, day, hour, 0, 0, 0)
+  }
+
+  #[test]
+  fn test01_length_and_during() {
+    let interval = TimeInterval::starting_from(at(1, 1, 0), Duration::hours(36)).unwrap();
+    assert_eq!(interval.as_end(), Some(&at(1, 2, 12)));
+    assert_eq!(
+      interval.length().unwrap().in_base_units(),
+      Duration::hours(36).in_base_units()
+    );
+    assert!(interval.is_during(&at(1, 1, 0)));
+    assert!(!interval.is_during(&at(1, 2, 12)));
+    assert!(interval.is_before(&at(1, 2, 12)));
+    assert!(interval.is_after(&at(1, 1, 0).subtract(Duration::milliseconds(1))));
+
+    let preceding = TimeInterval::preceding(at(3, 1, 0), Duration::months(1)).unwrap();
+    assert_eq!(preceding.as_start(), Some(&at(2, 1, 0)));
+    assert_eq!(
+      preceding.length().unwrap().in_base_units(),
+      Duration::days(29).in_base_units()
+    );
+    assert!(preceding.intersects(&TimeInterval::closed(at(2, 29, 0), at(4, 1, 0))));
+
+    assert_eq!(
+      TimeInterval::starting_from(TimePoint::new(0), Duration::hours(-1)),
+      Err(TimeIntervalError::NonPositiveLengthError)
+    );
+    assert_eq!(
+      TimeInterval::preceding(at(3, 1, 0), Duration::days(0)),
+      Err(TimeIntervalError::NonPositiveLengthError)
+    );
+  }
+
+  #[test]
+  fn test02_days_iterator() {
+    let days = TimeInterval::closed(at(1, 30, 6), at(2, 2, 6))
+      .days_iterator()
+      .unwrap()
+      .collect::<Vec<_>>();
+    assert_eq!(
+      days,
+      vec![at(1, 30, 6), at(1, 31, 6), at(2, 1, 6), at(2, 2, 6)]
+    );
+
+    let days = TimeInterval::right_open(at(1, 30, 6), at(2, 2, 6))
+      .days_iterator()
+      .unwrap()
+      .count();
+    assert_eq!(days, 3);
+
+    let unbounded = TimeInterval::over(
+      LimitValue::Limitless,
+      false,
+      LimitValue::Limit(at(1, 1, 0)),
+      false,
+    );
+    assert_eq!(
+      unbounded.days_iterator().err(),
+      Some(TimeIntervalError::UnboundedError)
+    );
+  }
+
+  #[test]
+  fn test03_subintervals_by() {
+    let quarter = TimeInterval::right_open(at(1, 31, 0), at(4, 30, 0));
+    let months = quarter
+      .subintervals_by(Duration::months(1))
+      .unwrap()
+      .collect::<Vec<_>>();
+    assert_eq!(
+      months,
+      vec![
+        TimeInterval::right_open(at(1, 31, 0), at(2, 29, 0)),
+        TimeInterval::right_open(at(2, 29, 0), at(3, 31, 0)),
+        TimeInterval::right_open(at(3, 31, 0), at(4, 30, 0)),
+      ]
+    );
+
+    let hours = TimeInterval::right_open(at(1, 1, 0), at(1, 1, 5))
+      .subintervals_by(Duration::hours(2))
+      .unwrap()
+      .count();
+    assert_eq!(hours, 2);
+
+    let open_ended = TimeInterval::everafter(at(1, 1, 0));
+    let mut days = open_ended.subintervals_by(Duration::days(1)).unwrap();
+    assert_eq!(
+      days.nth(9),
+      Some(TimeInterval::right_open(at(1, 10, 0), at(1, 11, 0)))
+    );
+
+    assert_eq!(
+      open_ended.subintervals_by(Duration::days(0)).err(),
+      Some(TimeIntervalError::NonPositiveLengthError)
+    );
+    assert_eq!(
+      open_ended.subintervals_by(Duration::hours(-1)).err(),
+      Some(TimeIntervalError::NonPositiveLengthError)
+    );
+    assert_eq!(open_ended.length(), Err(TimeIntervalError::UnboundedError));
+  }
+
+  #[test]
+  fn test04_display() {
+    let interval = TimeInterval::right_open(at(1, 1, 0), at(1, 2, 0));
+    assert_eq!(
+      interval.to_string(),
+      "[2024-01-01 00:00:00 +00:00, 2024-01-02 00:00:00 +00:00)"
+    );
+    assert_eq!(
+      interval.to_string_in(FixedOffset::east_opt(9 * 3600).unwrap()),
+      "[2024-01-01 09:00:00 +09:00, 2024-01-02 09:00:00 +09:00)"
+    );
+    assert_eq!(
+      TimeInterval::everafter(at(1, 1, 0)).to_string(),
+      "[2024-01-01 00:00:00 +00:00, +∞)"
+    );
+  }
+
+  #[test]
+  fn test05_out_of_range() {
+    let start = at(1, 1, 0);
+    assert_eq!(
+      Duration::months(i64::MAX).checked_added_to(start.clone()),
+      None
+    );
+    assert_eq!(
+      Duration::months(i64::MAX).added_to(start.clone()),
+      TimePoint::from(DateTime::<Utc>::MAX_UTC)
+    );
+    assert_eq!(
+      Duration::days(i64::MAX).subtracted_from(start.clone()),
+      TimePoint::from(DateTime::<Utc>::MIN_UTC)
+    );
+
+    let years = TimeInterval::everafter(start)
+      .subintervals_by(Duration::years(100_000))
+      .unwrap()
+      .count();
+    assert_eq!(years, 2);
+  }
+}
@@ -1,4 +1,6 @@
-use crate::time::{CalendarDate, CalendarDateTime, HourOfDay, MinuteOfHour};
+use chrono::TimeZone;
+
+use crate::time::{CalendarDate, CalendarDateTime, HourOfDay, MinuteOfHour, TimeSource};
 
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Hash)]
 pub struct TimeOfDay {
@@ -17,6 +19,15 @@ impl TimeOfDay {
     Self { hour, minute }
   }
 
+  /// 時計 `source` の現在の時点の、タイムゾーン `time_zone` での時刻を返す。
+  pub fn now<S, T>(source: &S, time_zone: T) -> Self
+  where
+    S: TimeSource + ?Sized,
+    T: TimeZone,
+  {
+    source.now().to_time_of_day(time_zone)
+  }
+
   pub fn breach_encapsulation_of_hour(&self) -> &HourOfDay {
     &self.hour
   }
@@ -3,11 +3,15 @@ use core::fmt;
 use chrono::*;
 use num::ToPrimitive;
 
-use crate::time::{CalendarDate, CalendarYearMonth, DayOfMonth, TimeOfDay};
+use crate::time::zoned_date_time::resolve_local;
+use crate::time::{
+  CalendarDate, CalendarYearMonth, DayOfMonth, GapPolicy, OverlapPolicy, TimeOfDay,
+  ZonedDateTimeError,
+};
 use crate::time::duration::Duration;
 
 /// TimePoint
-#[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Hash)]
+#[derive(Debug, Default, Clone, Eq, PartialEq, PartialOrd, Hash)]
 pub struct TimePoint(i64);
 
 impl From<i64> for TimePoint {
@@ -57,6 +61,10 @@ impl TimePoint {
     Self::at_ymd_hms_milli_tz(year, month, day, hour, minute, second, millisecond, Utc)
   }
 
+  /// タイムゾーン `time_zone` での現地日時の時点を返す。
+  ///
+  /// 存在しない日付、または夏時間の切り替えで存在しない・2回現れる現地時刻はパニックする。
+  /// 明示的に扱う場合は [TimePoint::try_at_ymd_hms_milli_tz] を使う。
   pub fn at_ymd_hms_milli_tz<T>(
     year: i32,
     month: u32,
@@ -70,11 +78,48 @@ impl TimePoint {
   where
     T: TimeZone,
   {
-    let milliseconds_from_epoc = time_zone
-      .ymd(year, month, day)
-      .and_hms_milli(hour, minute, second, millisecond)
-      .timestamp_millis();
-    Self::new(milliseconds_from_epoc)
+    Self::try_at_ymd_hms_milli_tz(
+      year,
+      month,
+      day,
+      hour,
+      minute,
+      second,
+      millisecond,
+      time_zone,
+      GapPolicy::Error,
+      OverlapPolicy::Error,
+    )
+    .unwrap_or_else(|err| {
+      panic!(
+        "{}-{}-{} {}:{}:{}.{}: {:?}",
+        year, month, day, hour, minute, second, millisecond, err
+      )
+    })
+  }
+
+  /// タイムゾーン `time_zone` での現地日時を、夏時間のポリシーに従って時点に解決する。
+  ///
+  /// 存在しない日付の場合、またはポリシーが `Error` の場合はエラーを返す。
+  pub fn try_at_ymd_hms_milli_tz<T>(
+    year: i32,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+    time_zone: T,
+    gap_policy: GapPolicy,
+    overlap_policy: OverlapPolicy,
+  ) -> Result<Self, ZonedDateTimeError>
+  where
+    T: TimeZone,
+  {
+    let naive = NaiveDate::from_ymd_opt(year, month, day)
+      .and_then(|e| e.and_hms_milli_opt(hour, minute, second, millisecond))
+      .ok_or(ZonedDateTimeError::InvalidDateTimeError)?;
+    resolve_local(&naive, &time_zone, gap_policy, overlap_policy)
   }
 
   pub fn at_cym_dom_hms_milli_tz<T>(
@@ -105,19 +150,26 @@ impl TimePoint {
     Self::at_midnight_cd_tz(calendar_date, Utc)
   }
 
+  /// タイムゾーン `time_zone` での日付 `calendar_date` の始まりの時点を返す。
+  ///
+  /// 夏時間の切り替えで 0:00 が存在しない場合はギャップの直後、2回現れる場合は早い方とする。
   pub fn at_midnight_cd_tz<T>(calendar_date: CalendarDate, time_zone: T) -> Self
   where
     T: TimeZone,
   {
-    Self::at_cym_dom_hms_milli_tz(
-      calendar_date.as_year_month().clone(),
-      calendar_date.as_day().clone(),
+    Self::try_at_ymd_hms_milli_tz(
+      calendar_date.as_year_month().to_year(),
+      calendar_date.as_year_month().to_month_u32(),
+      calendar_date.as_day().to_u32(),
       0,
       0,
       0,
       0,
       time_zone,
+      GapPolicy::ShiftForward,
+      OverlapPolicy::Earlier,
     )
+    .unwrap_or_else(|err| panic!("{:?}: {:?}", calendar_date, err))
   }
 
   pub fn parse_utc(date_time_str: &str, pattern: &str) -> Result<TimePoint, ParseError> {
@@ -272,8 +324,9 @@ impl TimePoint {
 #[cfg(test)]
 mod tests {
   use chrono::Utc;
+  use chrono_tz::America::New_York;
 
-  use crate::time::{TimeOfDay, TimePoint};
+  use crate::time::{GapPolicy, OverlapPolicy, TimeOfDay, TimePoint, ZonedDateTimeError};
 
   #[test]
   fn from_from_date_time() {
@@ -292,6 +345,51 @@ mod tests {
     assert_eq!(tp2, tp1)
   }
 
+  #[test]
+  fn try_at_ymd_hms_milli_tz() {
+    let at = |day, hour, gap_policy, overlap_policy| {
+      TimePoint::try_at_ymd_hms_milli_tz(
+        2024,
+        3,
+        day,
+        hour,
+        30,
+        0,
+        0,
+        New_York,
+        gap_policy,
+        overlap_policy,
+      )
+    };
+    assert_eq!(
+      at(10, 2, GapPolicy::Error, OverlapPolicy::Error),
+      Err(ZonedDateTimeError::NonexistentLocalTimeError)
+    );
+    assert_eq!(
+      at(10, 2, GapPolicy::ShiftForward, OverlapPolicy::Error),
+      Ok(TimePoint::at_ymd_hms_milli_utc(2024, 3, 10, 7, 30, 0, 0))
+    );
+    assert_eq!(
+      at(32, 2, GapPolicy::ShiftForward, OverlapPolicy::Earlier),
+      Err(ZonedDateTimeError::InvalidDateTimeError)
+    );
+    assert_eq!(
+      TimePoint::try_at_ymd_hms_milli_tz(
+        2024,
+        11,
+        3,
+        1,
+        30,
+        0,
+        0,
+        New_York,
+        GapPolicy::Error,
+        OverlapPolicy::Error,
+      ),
+      Err(ZonedDateTimeError::AmbiguousLocalTimeError)
+    );
+  }
+
   #[test]
   fn to_time_of_day() {
     let tp1 = TimePoint::at_ymd_hms_milli_utc(2010, 1, 1, 0, 0, 0, 0);
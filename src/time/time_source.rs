@@ -0,0 +1,143 @@
+use std::sync::Mutex;
+
+use chrono::Utc;
+
+use crate::time::{Duration, TimePoint};
+
+/// 現在の時点を返すもの。
+///
+/// ドメインのコードは `Utc::now()` を直接呼ばずにこのトレイトを通すことで、テストで時刻を固定できる。
+pub trait TimeSource {
+  fn now(&self) -> TimePoint;
+}
+
+/// システムの時計。
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct SystemClock;
+
+impl TimeSource for SystemClock {
+  fn now(&self) -> TimePoint {
+    TimePoint::from(Utc::now())
+  }
+}
+
+/// 常に同じ時点を返す時計。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FixedClock(TimePoint);
+
+impl FixedClock {
+  pub fn new(time_point: TimePoint) -> Self {
+    Self(time_point)
+  }
+}
+
+impl TimeSource for FixedClock {
+  fn now(&self) -> TimePoint {
+    self.0.clone()
+  }
+}
+
+/// 手動で進める時計。
+#[derive(Debug, Default)]
+pub struct ManualClock(Mutex<TimePoint>);
+
+impl ManualClock {
+  pub fn new(time_point: TimePoint) -> Self {
+    Self(Mutex::new(time_point))
+  }
+
+  /// 現在の時点を `time_point` にする。
+  pub fn set(&self, time_point: TimePoint) {
+    *self.0.lock().unwrap() = time_point;
+  }
+
+  /// 現在の時点を `duration` だけ進める。
+  pub fn advance(&self, duration: Duration) {
+    let mut current = self.0.lock().unwrap();
+    *current = current.clone().add(duration);
+  }
+}
+
+impl TimeSource for ManualClock {
+  fn now(&self) -> TimePoint {
+    self.0.lock().unwrap().clone()
+  }
+}
+
+/// 元の時計から `offset` だけずらした時計。
+#[derive(Debug, Clone)]
+pub struct OffsetClock<S: TimeSource> {
+  source: S,
+  offset: Duration,
+}
+
+impl<S: TimeSource> OffsetClock<S> {
+  pub fn new(source: S, offset: Duration) -> Self {
+    Self { source, offset }
+  }
+}
+
+impl<S: TimeSource> TimeSource for OffsetClock<S> {
+  fn now(&self) -> TimePoint {
+    self.source.now().add(self.offset.clone())
+  }
+}
+
+impl<S: TimeSource + ?Sized> TimeSource for &S {
+  fn now(&self) -> TimePoint {
+    (**self).now()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::FixedOffset;
+
+  use crate::time::{
+    CalendarDate, Duration, FixedClock, ManualClock, OffsetClock, SystemClock, TimeOfDay,
+    TimePoint, TimeSource,
+  };
+
+  #[test]
+  fn test01_system_clock() {
+    let before = TimePoint::from(chrono::Utc::now());
+    let now = SystemClock.now();
+    assert!(!now.is_before(&before));
+  }
+
+  #[test]
+  fn test02_fixed_and_offset_clock() {
+    let fixed = FixedClock::new(TimePoint::at_ymd_hms_milli_utc(2024, 10, 17, 15, 30, 0, 0));
+    assert_eq!(fixed.now(), fixed.now());
+
+    let jst = FixedOffset::east_opt(9 * 3600).unwrap();
+    assert_eq!(
+      CalendarDate::today(&fixed, jst),
+      CalendarDate::from((2024, 10, 18))
+    );
+    assert_eq!(TimeOfDay::now(&fixed, jst), TimeOfDay::from((0, 30)));
+
+    let offset = OffsetClock::new(&fixed, Duration::hours(-1));
+    assert_eq!(
+      offset.now(),
+      TimePoint::at_ymd_hms_milli_utc(2024, 10, 17, 14, 30, 0, 0)
+    );
+  }
+
+  #[test]
+  fn test03_manual_clock() {
+    let clock = ManualClock::new(TimePoint::at_ymd_hms_milli_utc(2024, 1, 31, 0, 0, 0, 0));
+    clock.advance(Duration::days(1));
+    assert_eq!(
+      clock.now(),
+      TimePoint::at_ymd_hms_milli_utc(2024, 2, 1, 0, 0, 0, 0)
+    );
+    clock.advance(Duration::months(1));
+    assert_eq!(
+      clock.now(),
+      TimePoint::at_ymd_hms_milli_utc(2024, 3, 1, 0, 0, 0, 0)
+    );
+    clock.set(TimePoint::new(0));
+    assert_eq!(clock.now(), TimePoint::new(0));
+  }
+}
@@ -0,0 +1,313 @@
+use std::fmt;
+
+use chrono::offset::LocalResult;
+use chrono::{Duration as OldDuration, NaiveDate, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
+
+use crate::time::{CalendarDate, CalendarDateTime, TimeOfDay, TimePoint};
+
+#[derive(Debug, PartialEq)]
+pub enum ZonedDateTimeError {
+  /// 夏時間の切り替えで存在しない現地時刻。
+  NonexistentLocalTimeError,
+  /// 夏時間の切り替えで2回現れる現地時刻。
+  AmbiguousLocalTimeError,
+  /// タイムゾーンのデータベースにない名前。
+  UnknownZoneError,
+  /// 存在しない日付または時刻。
+  InvalidDateTimeError,
+}
+
+/// 存在しない現地時刻（夏時間のギャップ）の扱い。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GapPolicy {
+  /// ギャップの長さだけ後ろにずらす（例: 2:30 が 3:30 になる）。
+  ShiftForward,
+  /// エラーにする。
+  Error,
+}
+
+/// 2回現れる現地時刻（夏時間のオーバーラップ）の扱い。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum OverlapPolicy {
+  /// 早い方の時点（切り替え前のオフセット）。
+  Earlier,
+  /// 遅い方の時点（切り替え後のオフセット）。
+  Later,
+  /// エラーにする。
+  Error,
+}
+
+/// タイムゾーン付きの日時。
+///
+/// 時点とタイムゾーンの組で、現地の日時はタイムゾーンのデータベースから求める。
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ZonedDateTime {
+  time_point: TimePoint,
+  zone: Tz,
+}
+
+impl fmt::Display for ZonedDateTime {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{}[{}]",
+      self
+        .time_point
+        .to_date_time(self.zone)
+        .format("%Y-%m-%dT%H:%M:%S%:z"),
+      self.zone.name()
+    )
+  }
+}
+
+impl ZonedDateTime {
+  /// 時点 `time_point` をタイムゾーン `zone` で表す。
+  pub fn new(time_point: TimePoint, zone: Tz) -> Self {
+    Self { time_point, zone }
+  }
+
+  /// タイムゾーン `zone` での現地日時 `date_time` を、ポリシーに従って時点に解決する。
+  pub fn of(
+    date_time: &CalendarDateTime,
+    zone: Tz,
+    gap_policy: GapPolicy,
+    overlap_policy: OverlapPolicy,
+  ) -> Result<Self, ZonedDateTimeError> {
+    let naive = Self::to_naive_date_time(date_time);
+    let time_point = resolve_local(&naive, &zone, gap_policy, overlap_policy)?;
+    Ok(Self::new(time_point, zone))
+  }
+
+  /// タイムゾーン名（例: `Asia/Tokyo`）からタイムゾーンを返す。
+  pub fn parse_zone(name: &str) -> Result<Tz, ZonedDateTimeError> {
+    name
+      .parse::<Tz>()
+      .map_err(|_| ZonedDateTimeError::UnknownZoneError)
+  }
+
+  pub fn as_time_point(&self) -> &TimePoint {
+    &self.time_point
+  }
+
+  pub fn to_time_point(&self) -> TimePoint {
+    self.time_point.clone()
+  }
+
+  pub fn as_zone(&self) -> &Tz {
+    &self.zone
+  }
+
+  /// UTC からのオフセットを秒で返す。
+  pub fn offset_in_seconds(&self) -> i32 {
+    self
+      .time_point
+      .to_date_time(self.zone)
+      .offset()
+      .fix()
+      .local_minus_utc()
+  }
+
+  /// 現地の日時を返す。秒以下は切り捨てる。
+  pub fn to_calendar_date_time(&self) -> CalendarDateTime {
+    CalendarDateTime::new(self.to_calendar_date(), self.to_time_of_day())
+  }
+
+  pub fn to_calendar_date(&self) -> CalendarDate {
+    self.time_point.clone().into_calendar_date(self.zone)
+  }
+
+  pub fn to_time_of_day(&self) -> TimeOfDay {
+    self.time_point.to_time_of_day(self.zone)
+  }
+
+  /// 同じ時点をタイムゾーン `zone` で表す。
+  pub fn with_zone_same_instant(&self, zone: Tz) -> Self {
+    Self::new(self.time_point.clone(), zone)
+  }
+
+  /// 同じ現地日時をタイムゾーン `zone` で表す。秒以下も保つ。
+  pub fn with_zone_same_local(
+    &self,
+    zone: Tz,
+    gap_policy: GapPolicy,
+    overlap_policy: OverlapPolicy,
+  ) -> Result<Self, ZonedDateTimeError> {
+    let naive = self.time_point.to_date_time(self.zone).naive_local();
+    let time_point = resolve_local(&naive, &zone, gap_policy, overlap_policy)?;
+    Ok(Self::new(time_point, zone))
+  }
+
+  pub fn is_after(&self, other: &Self) -> bool {
+    self.time_point.is_after(&other.time_point)
+  }
+
+  pub fn is_before(&self, other: &Self) -> bool {
+    self.time_point.is_before(&other.time_point)
+  }
+
+  pub(crate) fn to_naive_date_time(date_time: &CalendarDateTime) -> NaiveDateTime {
+    let date = date_time.as_date();
+    let time = date_time.as_time();
+    NaiveDate::from_ymd_opt(
+      date.as_year_month().to_year(),
+      date.as_year_month().to_month_u32(),
+      date.as_day().to_u32(),
+    )
+    .and_then(|e| {
+      e.and_hms_opt(
+        time.breach_encapsulation_of_hour().to_u32(),
+        time.breach_encapsulation_of_minute().to_u32(),
+        0,
+      )
+    })
+    .unwrap_or_else(|| panic!("{:?} is not a valid date time", date_time))
+  }
+}
+
+/// タイムゾーン `time_zone` での現地日時 `naive` を、ポリシーに従って時点に解決する。
+pub(crate) fn resolve_local<T: TimeZone>(
+  naive: &NaiveDateTime,
+  time_zone: &T,
+  gap_policy: GapPolicy,
+  overlap_policy: OverlapPolicy,
+) -> Result<TimePoint, ZonedDateTimeError> {
+  match time_zone.from_local_datetime(naive) {
+    LocalResult::Single(date_time) => Ok(TimePoint::from(date_time)),
+    LocalResult::Ambiguous(earlier, later) => match overlap_policy {
+      OverlapPolicy::Earlier => Ok(TimePoint::from(earlier)),
+      OverlapPolicy::Later => Ok(TimePoint::from(later)),
+      OverlapPolicy::Error => Err(ZonedDateTimeError::AmbiguousLocalTimeError),
+    },
+    LocalResult::None => match gap_policy {
+      GapPolicy::ShiftForward => {
+        shift_forward(naive, time_zone).ok_or(ZonedDateTimeError::NonexistentLocalTimeError)
+      }
+      GapPolicy::Error => Err(ZonedDateTimeError::NonexistentLocalTimeError),
+    },
+  }
+}
+
+/// ギャップの直前のオフセットで解釈した時点を返す。
+fn shift_forward<T: TimeZone>(naive: &NaiveDateTime, time_zone: &T) -> Option<TimePoint> {
+  let offset = (1..=24)
+    .find_map(|hours| {
+      time_zone
+        .from_local_datetime(&(*naive - OldDuration::hours(hours)))
+        .single()
+    })
+    .map(|e| e.offset().fix())?;
+  offset
+    .from_local_datetime(naive)
+    .single()
+    .map(TimePoint::from)
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono_tz::America::New_York;
+  use chrono_tz::Asia::Tokyo;
+  use chrono_tz::Europe::London;
+
+  use crate::time::{
+    CalendarDateTime, GapPolicy, OverlapPolicy, TimeOfDay, TimePoint, ZonedDateTime,
+    ZonedDateTimeError,
+  };
+
+  fn local(month: u32, day: u32, hour: u32, minute: u32) -> CalendarDateTime {
+    CalendarDateTime::from((2024, month, day, hour, minute))
+  }
+
+  fn utc(month: u32, day: u32, hour: u32, minute: u32) -> TimePoint {
+    TimePoint::at_ymd_hms_milli_utc(2024, month, day, hour, minute, 0, 0)
+  }
+
+  #[test]
+  fn test01_of() {
+    let zdt = ZonedDateTime::of(
+      &local(10, 17, 9, 0),
+      Tokyo,
+      GapPolicy::Error,
+      OverlapPolicy::Error,
+    )
+    .unwrap();
+    assert_eq!(zdt.as_time_point(), &utc(10, 17, 0, 0));
+    assert_eq!(zdt.offset_in_seconds(), 9 * 3600);
+    assert_eq!(zdt.to_calendar_date_time(), local(10, 17, 9, 0));
+    assert_eq!(zdt.to_string(), "2024-10-17T09:00:00+09:00[Asia/Tokyo]");
+  }
+
+  #[test]
+  fn test02_gap() {
+    // 2024-03-10 02:00 に 03:00 へ進む
+    let gap = local(3, 10, 2, 30);
+    assert_eq!(
+      ZonedDateTime::of(&gap, New_York, GapPolicy::Error, OverlapPolicy::Earlier),
+      Err(ZonedDateTimeError::NonexistentLocalTimeError)
+    );
+    let shifted = ZonedDateTime::of(
+      &gap,
+      New_York,
+      GapPolicy::ShiftForward,
+      OverlapPolicy::Earlier,
+    )
+    .unwrap();
+    assert_eq!(shifted.as_time_point(), &utc(3, 10, 7, 30));
+    assert_eq!(shifted.to_time_of_day(), TimeOfDay::from((3, 30)));
+  }
+
+  #[test]
+  fn test03_overlap() {
+    // 2024-11-03 02:00 に 01:00 へ戻る
+    let overlap = local(11, 3, 1, 30);
+    let of = |policy| ZonedDateTime::of(&overlap, New_York, GapPolicy::Error, policy);
+    assert_eq!(
+      of(OverlapPolicy::Earlier).unwrap().as_time_point(),
+      &utc(11, 3, 5, 30)
+    );
+    let later = of(OverlapPolicy::Later).unwrap();
+    assert_eq!(later.as_time_point(), &utc(11, 3, 6, 30));
+    assert_eq!(later.offset_in_seconds(), -5 * 3600);
+    assert_eq!(
+      of(OverlapPolicy::Error),
+      Err(ZonedDateTimeError::AmbiguousLocalTimeError)
+    );
+  }
+
+  #[test]
+  fn test04_conversions() {
+    let tokyo = ZonedDateTime::new(utc(7, 1, 3, 0), Tokyo);
+    let london = tokyo.with_zone_same_instant(London);
+    assert_eq!(london.as_time_point(), tokyo.as_time_point());
+    assert_eq!(london.to_calendar_date_time(), local(7, 1, 4, 0));
+    assert_eq!(
+      london.to_string(),
+      "2024-07-01T04:00:00+01:00[Europe/London]"
+    );
+
+    let same_local = tokyo
+      .with_zone_same_local(London, GapPolicy::Error, OverlapPolicy::Error)
+      .unwrap();
+    assert_eq!(same_local.to_calendar_date_time(), local(7, 1, 12, 0));
+    assert!(same_local.is_after(&tokyo));
+
+    // 秒以下も同じ現地時刻に保つ
+    let tokyo = ZonedDateTime::new(
+      TimePoint::at_ymd_hms_milli_utc(2024, 7, 1, 0, 0, 30, 250),
+      Tokyo,
+    );
+    let same_local = tokyo
+      .with_zone_same_local(London, GapPolicy::Error, OverlapPolicy::Error)
+      .unwrap();
+    assert_eq!(
+      same_local.to_time_point(),
+      TimePoint::at_ymd_hms_milli_utc(2024, 7, 1, 8, 0, 30, 250)
+    );
+
+    assert_eq!(ZonedDateTime::parse_zone("Asia/Tokyo"), Ok(Tokyo));
+    assert_eq!(
+      ZonedDateTime::parse_zone("Asia/Nowhere"),
+      Err(ZonedDateTimeError::UnknownZoneError)
+    );
+  }
+}
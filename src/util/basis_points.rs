@@ -0,0 +1,128 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+
+use crate::util::{ParseRateError, Percentage, Ratio};
+
+/// ベーシスポイント（1bp = 0.01% = 0.0001）。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct BasisPoints(Decimal);
+
+impl fmt::Display for BasisPoints {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}bp", self.0.normalize())
+  }
+}
+
+impl FromStr for BasisPoints {
+  type Err = ParseRateError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    let value = s
+      .strip_suffix("bps")
+      .or_else(|| s.strip_suffix("bp"))
+      .ok_or(ParseRateError::MissingSuffixError)?;
+    Decimal::from_str(value.trim())
+      .map(BasisPoints::new)
+      .map_err(|_| ParseRateError::InvalidNumberError)
+  }
+}
+
+impl From<Ratio> for BasisPoints {
+  fn from(ratio: Ratio) -> Self {
+    Self::new(ratio.decimal_value(0, None) * Self::PER_UNIT)
+  }
+}
+
+impl From<BasisPoints> for Ratio {
+  fn from(basis_points: BasisPoints) -> Self {
+    Ratio::new(basis_points.0, BasisPoints::PER_UNIT)
+  }
+}
+
+impl From<Percentage> for BasisPoints {
+  fn from(percentage: Percentage) -> Self {
+    Self::new(percentage.to_decimal() * Decimal::ONE_HUNDRED)
+  }
+}
+
+impl BasisPoints {
+  const PER_UNIT: Decimal = Decimal::from_parts(10000, 0, 0, false, 0);
+
+  pub fn new(value: Decimal) -> Self {
+    Self(value)
+  }
+
+  /// ベーシスポイントの値（25bp なら 25）を返す。
+  pub fn to_decimal(&self) -> Decimal {
+    self.0
+  }
+
+  /// 乗数としての値（25bp なら 0.0025）を返す。
+  pub fn to_factor(&self) -> Decimal {
+    self.0 / Self::PER_UNIT
+  }
+
+  pub fn to_ratio(&self) -> Ratio {
+    Ratio::from(*self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::{Decimal, RoundingStrategy};
+
+  use crate::money::Money;
+  use crate::util::{BasisPoints, ParseRateError, Percentage, Ratio};
+
+  fn dec(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap()
+  }
+
+  #[test]
+  fn test01_parse_and_display() {
+    assert_eq!(
+      BasisPoints::from_str("25bp"),
+      Ok(BasisPoints::new(dec("25")))
+    );
+    assert_eq!(
+      BasisPoints::from_str("12.5 bps"),
+      Ok(BasisPoints::new(dec("12.5")))
+    );
+    assert_eq!(
+      BasisPoints::from_str("25%"),
+      Err(ParseRateError::MissingSuffixError)
+    );
+    assert_eq!(BasisPoints::new(dec("25")).to_string(), "25bp");
+  }
+
+  #[test]
+  fn test02_conversion() {
+    let bp = BasisPoints::new(dec("25"));
+    assert_eq!(bp.to_factor(), dec("0.0025"));
+    assert_eq!(Percentage::from(bp), Percentage::new(dec("0.25")));
+    assert_eq!(
+      BasisPoints::from(Percentage::new(dec("1.5"))),
+      BasisPoints::new(dec("150"))
+    );
+    assert_eq!(
+      BasisPoints::from(Ratio::new_i64(1, 100)),
+      BasisPoints::new(dec("100"))
+    );
+    assert_eq!(bp.to_ratio().decimal_value(4, None), dec("0.0025"));
+  }
+
+  #[test]
+  fn test03_money_times_basis_points() {
+    let principal = Money::from(("1000000", CurrencyCode::USD));
+    assert_eq!(
+      principal.times_basis_points(BasisPoints::new(dec("25")), RoundingStrategy::ToZero),
+      Money::from(("2500", CurrencyCode::USD))
+    );
+  }
+}
@@ -1,3 +1,7 @@
+mod basis_points;
+mod percentage;
 mod ratio;
 
+pub use self::basis_points::*;
+pub use self::percentage::*;
 pub use self::ratio::*;
@@ -0,0 +1,183 @@
+use std::fmt;
+use std::str::FromStr;
+
+use rust_decimal::prelude::Zero;
+use rust_decimal::Decimal;
+
+use crate::money::{Money, MoneyError};
+use crate::util::{BasisPoints, Ratio};
+
+#[derive(Debug, PartialEq)]
+pub enum ParseRateError {
+  MissingSuffixError,
+  InvalidNumberError,
+}
+
+/// 百分率（例: 7.5% は `Percentage::new(7.5)`）。
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Percentage(Decimal);
+
+impl fmt::Display for Percentage {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}%", self.0.normalize())
+  }
+}
+
+impl FromStr for Percentage {
+  type Err = ParseRateError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let value = s
+      .trim()
+      .strip_suffix('%')
+      .ok_or(ParseRateError::MissingSuffixError)?;
+    Decimal::from_str(value.trim())
+      .map(Percentage::new)
+      .map_err(|_| ParseRateError::InvalidNumberError)
+  }
+}
+
+impl From<Ratio> for Percentage {
+  fn from(ratio: Ratio) -> Self {
+    Self::new(ratio.decimal_value(0, None) * Self::HUNDRED)
+  }
+}
+
+impl From<Percentage> for Ratio {
+  fn from(percentage: Percentage) -> Self {
+    Ratio::new(percentage.0, Percentage::HUNDRED)
+  }
+}
+
+impl From<BasisPoints> for Percentage {
+  fn from(basis_points: BasisPoints) -> Self {
+    Self::new(basis_points.to_decimal() / Self::HUNDRED)
+  }
+}
+
+impl Percentage {
+  const HUNDRED: Decimal = Decimal::ONE_HUNDRED;
+
+  pub fn new(value: Decimal) -> Self {
+    Self(value)
+  }
+
+  pub fn zero() -> Self {
+    Self::new(Decimal::zero())
+  }
+
+  /// 百分率の値（7.5% なら 7.5）を返す。
+  pub fn to_decimal(&self) -> Decimal {
+    self.0
+  }
+
+  /// 乗数としての値（7.5% なら 0.075）を返す。
+  pub fn to_factor(&self) -> Decimal {
+    self.0 / Self::HUNDRED
+  }
+
+  pub fn to_ratio(&self) -> Ratio {
+    Ratio::from(*self)
+  }
+
+  /// `from` から `to` への変化率を返す。丸めは行わない。
+  ///
+  /// 通貨が異なる場合、または `from` が0の場合はエラーを返す。
+  pub fn change_between(from: &Money, to: &Money) -> Result<Self, MoneyError> {
+    if from.currency != to.currency {
+      return Err(MoneyError::NotSameCurrencyError);
+    }
+    if from.is_zero() {
+      return Err(MoneyError::DivisionByZeroError);
+    }
+    Ok(Self::new(
+      (to.amount - from.amount) / from.amount * Self::HUNDRED,
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::str::FromStr;
+
+  use iso_4217::CurrencyCode;
+  use rust_decimal::{Decimal, RoundingStrategy};
+
+  use crate::money::{Money, MoneyError};
+  use crate::util::{ParseRateError, Percentage, Ratio};
+
+  fn dec(s: &str) -> Decimal {
+    Decimal::from_str(s).unwrap()
+  }
+
+  #[test]
+  fn test01_parse_and_display() {
+    assert_eq!(
+      Percentage::from_str("7.5%"),
+      Ok(Percentage::new(dec("7.5")))
+    );
+    assert_eq!(
+      Percentage::from_str(" -3 % "),
+      Ok(Percentage::new(dec("-3")))
+    );
+    assert_eq!(
+      Percentage::from_str("7.5"),
+      Err(ParseRateError::MissingSuffixError)
+    );
+    assert_eq!(
+      Percentage::from_str("abc%"),
+      Err(ParseRateError::InvalidNumberError)
+    );
+    assert_eq!(Percentage::new(dec("7.50")).to_string(), "7.5%");
+  }
+
+  #[test]
+  fn test02_ratio_conversion() {
+    let p = Percentage::from(Ratio::new_i64(3, 40));
+    assert_eq!(p, Percentage::new(dec("7.5")));
+    assert_eq!(p.to_ratio().decimal_value(3, None), dec("0.075"));
+    assert_eq!(p.to_factor(), dec("0.075"));
+  }
+
+  #[test]
+  fn test03_money_times_percentage() {
+    let price = Money::from((1985u32, CurrencyCode::JPY));
+    let tax = Percentage::new(dec("8"));
+    assert_eq!(
+      price
+        .clone()
+        .times_percentage(tax, RoundingStrategy::ToZero),
+      Money::from((158u32, CurrencyCode::JPY))
+    );
+    assert_eq!(
+      price.times_percentage(tax, RoundingStrategy::MidpointAwayFromZero),
+      Money::from((159u32, CurrencyCode::JPY))
+    );
+    assert_eq!(
+      (Money::from(("10", CurrencyCode::USD)) * Percentage::new(dec("12.5"))).amount,
+      dec("1.25")
+    );
+  }
+
+  #[test]
+  fn test04_change_between() {
+    let from = Money::from((200u32, CurrencyCode::USD));
+    let to = Money::from((250u32, CurrencyCode::USD));
+    assert_eq!(
+      Percentage::change_between(&from, &to),
+      Ok(Percentage::new(dec("25")))
+    );
+    assert_eq!(
+      Percentage::change_between(&to, &from),
+      Ok(Percentage::new(dec("-20")))
+    );
+    assert_eq!(
+      Percentage::change_between(&Money::zero(CurrencyCode::USD), &to),
+      Err(MoneyError::DivisionByZeroError)
+    );
+    assert_eq!(
+      Percentage::change_between(&from, &Money::from((250u32, CurrencyCode::JPY))),
+      Err(MoneyError::NotSameCurrencyError)
+    );
+  }
+}
@@ -22,6 +22,14 @@ impl Ratio {
     }
   }
 
+  pub fn to_numerator(&self) -> Decimal {
+    self.numerator
+  }
+
+  pub fn to_denominator(&self) -> Decimal {
+    self.denominator
+  }
+
   pub fn decimal_value(&self, scale: u32, rounding_strategy: Option<RoundingStrategy>) -> Decimal {
     let Ratio {
       numerator,